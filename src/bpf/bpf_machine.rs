@@ -0,0 +1,185 @@
+use crate::bpf::bpf_program::BpfProgram;
+use crate::bpf::inter::bpf_instruction::{BpfInstruction, BPF_A, BPF_ABS, BPF_ADD, BPF_ALU, BPF_AND, BPF_B, BPF_DIV, BPF_H, BPF_IMM, BPF_IND, BPF_JA, BPF_JEQ, BPF_JGE, BPF_JGT, BPF_JMP, BPF_JSET, BPF_LD, BPF_LDX, BPF_LEN, BPF_LSH, BPF_MEM, BPF_MEMWORDS, BPF_MISC, BPF_MOD, BPF_MSH, BPF_MUL, BPF_NEG, BPF_OR, BPF_RET, BPF_RSH, BPF_ST, BPF_STX, BPF_SUB, BPF_TAX, BPF_TXA, BPF_W, BPF_X, BPF_XOR};
+
+/*
+Runs classic BPF the way the kernel does, a load past the end of the packet or a division by zero rejects it.
+Programs should be verified first, anything unverifiable also rejects.
+*/
+#[derive(Clone, Debug)]
+pub struct BpfMachine {
+    accumulator: u32,
+    index: u32,
+    memory: [u32; BPF_MEMWORDS]
+}
+
+impl BpfMachine {
+
+    pub fn new() -> Self {
+        Self {
+            accumulator: 0,
+            index: 0,
+            memory: [0; BPF_MEMWORDS]
+        }
+    }
+
+    //WIRE LENGTH IS WHAT BPF_LEN LOADS, IT CAN BE LONGER THAN THE CAPTURED BYTES
+    pub fn run(&mut self, program: &BpfProgram, packet: &[u8], wire_length: u32) -> u32 {
+        self.accumulator = 0;
+        self.index = 0;
+        self.memory = [0; BPF_MEMWORDS];
+
+        let instructions = program.get_instructions();
+        let mut pc = 0;
+
+        while pc < instructions.len() {
+            let instruction = instructions[pc];
+            let k = instruction.get_k();
+            pc += 1;
+
+            match instruction.get_class() {
+                BPF_LD => {
+                    self.accumulator = match instruction.get_code() & 0xe0 {
+                        BPF_ABS => match Self::load(packet, k as usize, instruction) {
+                            Some(value) => value,
+                            None => return 0
+                        },
+                        BPF_IND => match Self::load(packet, self.index as usize + k as usize, instruction) {
+                            Some(value) => value,
+                            None => return 0
+                        },
+                        BPF_LEN => wire_length,
+                        BPF_IMM => k,
+                        BPF_MEM => match self.memory.get(k as usize) {
+                            Some(value) => *value,
+                            None => return 0
+                        },
+                        _ => return 0
+                    };
+                }
+                BPF_LDX => {
+                    self.index = match instruction.get_code() & 0xe0 {
+                        BPF_IMM => k,
+                        BPF_LEN => wire_length,
+                        BPF_MEM => match self.memory.get(k as usize) {
+                            Some(value) => *value,
+                            None => return 0
+                        },
+                        BPF_MSH => match packet.get(k as usize) {
+                            Some(value) => ((value & 0x0f) as u32) << 2,
+                            None => return 0
+                        },
+                        _ => return 0
+                    };
+                }
+                BPF_ST | BPF_STX => {
+                    let value = match instruction.get_class() {
+                        BPF_ST => self.accumulator,
+                        _ => self.index
+                    };
+
+                    match self.memory.get_mut(k as usize) {
+                        Some(slot) => *slot = value,
+                        None => return 0
+                    }
+                }
+                BPF_ALU => {
+                    let operand = match instruction.get_code() & BPF_X {
+                        BPF_X => self.index,
+                        _ => k
+                    };
+
+                    let a = self.accumulator;
+                    self.accumulator = match instruction.get_code() & 0xf0 {
+                        BPF_ADD => a.wrapping_add(operand),
+                        BPF_SUB => a.wrapping_sub(operand),
+                        BPF_MUL => a.wrapping_mul(operand),
+                        BPF_DIV => match operand {
+                            0 => return 0,
+                            _ => a / operand
+                        },
+                        BPF_MOD => match operand {
+                            0 => return 0,
+                            _ => a % operand
+                        },
+                        BPF_AND => a & operand,
+                        BPF_OR => a | operand,
+                        BPF_XOR => a ^ operand,
+                        BPF_LSH => a.checked_shl(operand).unwrap_or(0),
+                        BPF_RSH => a.checked_shr(operand).unwrap_or(0),
+                        BPF_NEG => a.wrapping_neg(),
+                        _ => return 0
+                    };
+                }
+                BPF_JMP => {
+                    let operand = match instruction.get_code() & BPF_X {
+                        BPF_X => self.index,
+                        _ => k
+                    };
+
+                    let taken = match instruction.get_code() & 0xf0 {
+                        BPF_JA => {
+                            pc += k as usize;
+                            continue;
+                        }
+                        BPF_JEQ => self.accumulator == operand,
+                        BPF_JGT => self.accumulator > operand,
+                        BPF_JGE => self.accumulator >= operand,
+                        BPF_JSET => self.accumulator & operand != 0,
+                        _ => return 0
+                    };
+
+                    pc += match taken {
+                        true => instruction.get_jt(),
+                        false => instruction.get_jf()
+                    } as usize;
+                }
+                BPF_RET => {
+                    return match instruction.get_code() & 0x18 {
+                        BPF_A => self.accumulator,
+                        BPF_X => self.index,
+                        _ => k
+                    };
+                }
+                BPF_MISC => {
+                    match instruction.get_code() & 0xf8 {
+                        BPF_TAX => self.index = self.accumulator,
+                        BPF_TXA => self.accumulator = self.index,
+                        _ => return 0
+                    }
+                }
+                _ => return 0
+            }
+        }
+
+        0
+    }
+
+    pub fn get_accumulator(&self) -> u32 {
+        self.accumulator
+    }
+
+    pub fn get_index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn get_memory(&self) -> &[u32; BPF_MEMWORDS] {
+        &self.memory
+    }
+
+    //NETWORK BYTE ORDER, NONE WHEN IT RUNS OFF THE END
+    fn load(packet: &[u8], offset: usize, instruction: BpfInstruction) -> Option<u32> {
+        match instruction.get_code() & 0x18 {
+            BPF_W => packet.get(offset..offset.checked_add(4)?).map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]])),
+            BPF_H => packet.get(offset..offset.checked_add(2)?).map(|b| u16::from_be_bytes([b[0], b[1]]) as u32),
+            BPF_B => packet.get(offset).map(|b| *b as u32),
+            _ => None
+        }
+    }
+}
+
+impl Default for BpfMachine {
+
+    fn default() -> Self {
+        Self::new()
+    }
+}
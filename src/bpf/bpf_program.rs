@@ -0,0 +1,298 @@
+use crate::bpf::bpf_machine::BpfMachine;
+use crate::bpf::filter_parser::FilterParser;
+use crate::bpf::inter::bpf_instruction::{BpfInstruction, BPF_A, BPF_ABS, BPF_ADD, BPF_ALU, BPF_AND, BPF_B, BPF_DIV, BPF_H, BPF_IMM, BPF_IND, BPF_JA, BPF_JEQ, BPF_JGE, BPF_JGT, BPF_JMP, BPF_JSET, BPF_K, BPF_LD, BPF_LDX, BPF_LEN, BPF_LSH, BPF_MAXINSNS, BPF_MEM, BPF_MEMWORDS, BPF_MISC, BPF_MOD, BPF_MSH, BPF_MUL, BPF_NEG, BPF_OR, BPF_RET, BPF_RSH, BPF_ST, BPF_STX, BPF_SUB, BPF_TAX, BPF_TXA, BPF_W, BPF_X, BPF_XOR};
+use crate::bpf::inter::filter_error::FilterError;
+use crate::bpf::inter::nodes::Nodes;
+
+pub const DEFAULT_SNAP_LENGTH: u32 = 262144;
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct BpfProgram {
+    instructions: Vec<BpfInstruction>
+}
+
+impl BpfProgram {
+
+    pub fn new(instructions: Vec<BpfInstruction>) -> Self {
+        Self {
+            instructions
+        }
+    }
+
+    pub fn compile(expression: &str) -> Result<Self, FilterError> {
+        Self::compile_with_snap_length(expression, DEFAULT_SNAP_LENGTH)
+    }
+
+    //SNAP LENGTH IS WHAT THE PROGRAM RETURNS FOR A MATCH, THE KERNEL TRUNCATES TO IT
+    pub fn compile_with_snap_length(expression: &str, snap_length: u32) -> Result<Self, FilterError> {
+        let node = FilterParser::new(expression)?.parse()?;
+        Self::from_node(&node, snap_length)
+    }
+
+    /*
+    Code is generated back to front so every jump target already exists when the jump is emitted.
+    Positions are counted from the end of the program, a jump at i to r skips i - r - 1 instructions.
+    */
+    pub fn from_node(node: &Nodes, snap_length: u32) -> Result<Self, FilterError> {
+        let mut reversed = vec![
+            BpfInstruction::statement(BPF_RET | BPF_K, 0),
+            BpfInstruction::statement(BPF_RET | BPF_K, snap_length)
+        ];
+
+        let entry = Self::generate(&mut reversed, node, 1, 0);
+
+        if entry != reversed.len() - 1 {
+            let offset = reversed.len() - entry - 1;
+            reversed.push(BpfInstruction::statement(BPF_JMP | BPF_JA, offset as u32));
+        }
+
+        if reversed.len() > BPF_MAXINSNS {
+            return Err(FilterError::TooLong {
+                length: reversed.len()
+            });
+        }
+
+        reversed.reverse();
+
+        Ok(Self {
+            instructions: reversed
+        })
+    }
+
+    fn generate(reversed: &mut Vec<BpfInstruction>, node: &Nodes, on_true: usize, on_false: usize) -> usize {
+        match node {
+            Nodes::And(left, right) => {
+                let right = Self::generate(reversed, right, on_true, on_false);
+                Self::generate(reversed, left, right, on_false)
+            }
+            Nodes::Or(left, right) => {
+                let right = Self::generate(reversed, right, on_true, on_false);
+                Self::generate(reversed, left, on_true, right)
+            }
+            Nodes::Not(node) => {
+                Self::generate(reversed, node, on_false, on_true)
+            }
+            Nodes::Test { loads, jump, k } => {
+                let on_true = Self::reach(reversed, on_true);
+                let on_false = Self::reach(reversed, on_false);
+
+                let i = reversed.len();
+                reversed.push(BpfInstruction::jump(*jump, *k, (i - on_true - 1) as u8, (i - on_false - 1) as u8));
+
+                for load in loads.iter().rev() {
+                    reversed.push(*load);
+                }
+
+                reversed.len() - 1
+            }
+            Nodes::True => on_true,
+            Nodes::False => on_false
+        }
+    }
+
+    //CONDITIONAL JUMPS ONLY REACH 255 INSTRUCTIONS, FURTHER TARGETS GO THROUGH AN UNCONDITIONAL JUMP
+    fn reach(reversed: &mut Vec<BpfInstruction>, target: usize) -> usize {
+        let i = reversed.len();
+
+        if i - target - 1 < u8::MAX as usize {
+            return target;
+        }
+
+        reversed.push(BpfInstruction::statement(BPF_JMP | BPF_JA, (i - target - 1) as u32));
+        i
+    }
+
+    pub fn get_instructions(&self) -> &Vec<BpfInstruction> {
+        &self.instructions
+    }
+
+    pub fn len(&self) -> usize {
+        self.instructions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instructions.is_empty()
+    }
+
+    /*
+    Same checks the kernel makes before attaching, every opcode known, every jump forward and inside the program,
+    scratch memory in range, no constant division by zero and a return at the end.
+    Ancillary loads (offsets past 0xfffff000) aren't supported.
+    */
+    pub fn verify(&self) -> Result<(), FilterError> {
+        if self.instructions.is_empty() {
+            return Err(FilterError::Empty);
+        }
+
+        if self.instructions.len() > BPF_MAXINSNS {
+            return Err(FilterError::TooLong {
+                length: self.instructions.len()
+            });
+        }
+
+        for (index, instruction) in self.instructions.iter().enumerate() {
+            let code = instruction.get_code();
+            let k = instruction.get_k();
+            let remaining = self.instructions.len() - index - 1;
+
+            let valid = match code {
+                c if c == BPF_LD | BPF_W | BPF_ABS || c == BPF_LD | BPF_H | BPF_ABS || c == BPF_LD | BPF_B | BPF_ABS ||
+                        c == BPF_LD | BPF_W | BPF_IND || c == BPF_LD | BPF_H | BPF_IND || c == BPF_LD | BPF_B | BPF_IND => k < 0xfffff000,
+                c if c == BPF_LD | BPF_W | BPF_LEN || c == BPF_LD | BPF_IMM || c == BPF_LDX | BPF_W | BPF_IMM ||
+                        c == BPF_LDX | BPF_W | BPF_LEN || c == BPF_LDX | BPF_B | BPF_MSH => true,
+                c if c == BPF_LD | BPF_MEM || c == BPF_LDX | BPF_MEM || c == BPF_ST || c == BPF_STX => {
+                    if k as usize >= BPF_MEMWORDS {
+                        return Err(FilterError::BadMemory {
+                            index
+                        });
+                    }
+
+                    true
+                }
+                c if c & 0x07 == BPF_ALU => {
+                    match c & 0xf0 {
+                        BPF_DIV | BPF_MOD if c & BPF_X == BPF_K && k == 0 => return Err(FilterError::DivisionByZero {
+                            index
+                        }),
+                        BPF_NEG => c == BPF_ALU | BPF_NEG,
+                        BPF_ADD | BPF_SUB | BPF_MUL | BPF_DIV | BPF_MOD | BPF_AND | BPF_OR | BPF_XOR | BPF_LSH | BPF_RSH => c & !0xf8 == BPF_ALU,
+                        _ => false
+                    }
+                }
+                c if c == BPF_JMP | BPF_JA => {
+                    if k as usize >= remaining {
+                        return Err(FilterError::BadJump {
+                            index
+                        });
+                    }
+
+                    true
+                }
+                c if c & 0x07 == BPF_JMP => {
+                    if instruction.get_jt() as usize >= remaining || instruction.get_jf() as usize >= remaining {
+                        return Err(FilterError::BadJump {
+                            index
+                        });
+                    }
+
+                    matches!(c & 0xf0, BPF_JEQ | BPF_JGT | BPF_JGE | BPF_JSET) && c & !0xf8 == BPF_JMP
+                }
+                c if c == BPF_RET | BPF_K || c == BPF_RET | BPF_A || c == BPF_RET | BPF_X => true,
+                c if c == BPF_MISC | BPF_TAX || c == BPF_MISC | BPF_TXA => true,
+                _ => false
+            };
+
+            if !valid {
+                return Err(FilterError::InvalidInstruction {
+                    index,
+                    code
+                });
+            }
+        }
+
+        match self.instructions.last() {
+            Some(instruction) if instruction.get_class() == BPF_RET => Ok(()),
+            _ => Err(FilterError::MissingReturn)
+        }
+    }
+
+    //RETURNS HOW MANY BYTES TO KEEP, 0 DROPS THE PACKET
+    pub fn run(&self, packet: &[u8], wire_length: u32) -> u32 {
+        BpfMachine::new().run(self, packet, wire_length)
+    }
+
+    pub fn matches(&self, packet: &[u8]) -> bool {
+        self.run(packet, packet.len() as u32) != 0
+    }
+
+    //SAME LAYOUT AS tcpdump -d
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        self.instructions.iter()
+            .enumerate()
+            .map(|(index, instruction)| format!("({:03}) {}", index, Self::disassemble(index, instruction)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn disassemble(index: usize, instruction: &BpfInstruction) -> String {
+        let code = instruction.get_code();
+        let k = instruction.get_k();
+
+        let operand = match code & BPF_X {
+            BPF_X => "x".to_string(),
+            _ => format!("#{:#x}", k)
+        };
+
+        let (op, argument) = match code & 0x07 {
+            BPF_LD | BPF_LDX => {
+                let op = match (code & 0x07, code & 0x18) {
+                    (BPF_LDX, BPF_B) => "ldxb",
+                    (BPF_LDX, _) => "ldx",
+                    (_, BPF_H) => "ldh",
+                    (_, BPF_B) => "ldb",
+                    _ => "ld"
+                };
+
+                let argument = match code & 0xe0 {
+                    BPF_ABS => format!("[{}]", k),
+                    BPF_IND => format!("[x + {}]", k),
+                    BPF_LEN => "#pktlen".to_string(),
+                    BPF_MEM => format!("M[{}]", k),
+                    BPF_MSH => format!("4*([{}]&0xf)", k),
+                    _ => format!("#{:#x}", k)
+                };
+
+                (op, argument)
+            }
+            BPF_ST => ("st", format!("M[{}]", k)),
+            BPF_STX => ("stx", format!("M[{}]", k)),
+            BPF_ALU => {
+                let op = match code & 0xf0 {
+                    BPF_ADD => "add",
+                    BPF_SUB => "sub",
+                    BPF_MUL => "mul",
+                    BPF_DIV => "div",
+                    BPF_MOD => "mod",
+                    BPF_AND => "and",
+                    BPF_OR => "or",
+                    BPF_XOR => "xor",
+                    BPF_LSH => "lsh",
+                    BPF_RSH => "rsh",
+                    BPF_NEG => return "neg".to_string(),
+                    _ => return format!("unimp {:#06x}", code)
+                };
+
+                (op, operand)
+            }
+            BPF_JMP => {
+                let op = match code & 0xf0 {
+                    BPF_JA => return format!("{:<8} {}", "ja", index + 1 + k as usize),
+                    BPF_JEQ => "jeq",
+                    BPF_JGT => "jgt",
+                    BPF_JGE => "jge",
+                    BPF_JSET => "jset",
+                    _ => return format!("unimp {:#06x}", code)
+                };
+
+                return format!("{:<8} {:<16} jt {}\tjf {}", op, operand, index + 1 + instruction.get_jt() as usize, index + 1 + instruction.get_jf() as usize);
+            }
+            BPF_RET => {
+                let argument = match code & 0x18 {
+                    BPF_A => "a".to_string(),
+                    BPF_X => "x".to_string(),
+                    _ => format!("#{}", k)
+                };
+
+                ("ret", argument)
+            }
+            _ => match code & 0xf8 {
+                BPF_TAX => return "tax".to_string(),
+                BPF_TXA => return "txa".to_string(),
+                _ => return format!("unimp {:#06x}", code)
+            }
+        };
+
+        format!("{:<8} {}", op, argument)
+    }
+}
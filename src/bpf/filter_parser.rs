@@ -0,0 +1,689 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use crate::bpf::inter::bpf_instruction::{BpfInstruction, BPF_ABS, BPF_ALU, BPF_AND, BPF_B, BPF_H, BPF_IND, BPF_JEQ, BPF_JGE, BPF_JGT, BPF_JMP, BPF_JSET, BPF_K, BPF_LD, BPF_LDX, BPF_LEN, BPF_MSH, BPF_W};
+use crate::bpf::inter::directions::Directions;
+use crate::bpf::inter::filter_error::FilterError;
+use crate::bpf::inter::nodes::Nodes;
+use crate::bpf::inter::tokens::Tokens;
+
+const PROTOCOLS: [&str; 8] = ["ether", "ip", "ip6", "arp", "tcp", "udp", "icmp", "icmp6"];
+const TYPES: [&str; 4] = ["host", "net", "port", "proto"];
+
+const ETHERNET_HEADER_SIZE: u32 = 14;
+const ETHER_TYPE_IPV4: u16 = 0x0800;
+const ETHER_TYPE_IPV6: u16 = 0x86dd;
+const ETHER_TYPE_ARP: u16 = 0x0806;
+const ETHER_TYPE_VLANS: [u16; 3] = [0x8100, 0x88a8, 0x9100];
+const VLAN_TAG_SIZE: u32 = 4;
+
+#[derive(Clone, Debug)]
+struct Qualifiers {
+    protocol: Option<String>,
+    direction: Directions,
+    _type: String
+}
+
+/*
+Parses tcpdump style expressions, "and" and "or" share a precedence and associate left to right like tcpdump.
+A bare value after "and" / "or" reuses the previous qualifiers, so "port 80 or 443" works.
+Like tcpdump, every "vlan" moves the rest of the expression past one more tag, so "vlan 10 and vlan 20 and icmp" matches QinQ.
+*/
+pub struct FilterParser {
+    tokens: Vec<(usize, Tokens)>,
+    position: usize,
+    last: Option<Qualifiers>,
+    link: u32
+}
+
+impl FilterParser {
+
+    pub fn new(expression: &str) -> Result<Self, FilterError> {
+        Ok(Self {
+            tokens: Tokens::tokenize(expression)?,
+            position: 0,
+            last: None,
+            link: 0
+        })
+    }
+
+    //AN EMPTY EXPRESSION MATCHES EVERYTHING
+    pub fn parse(&mut self) -> Result<Nodes, FilterError> {
+        if self.tokens.is_empty() {
+            return Ok(Nodes::True);
+        }
+
+        let node = self.parse_expression()?;
+
+        match self.tokens.get(self.position) {
+            Some((position, token)) => Err(FilterError::UnexpectedToken {
+                position: *position,
+                token: token.to_string()
+            }),
+            None => Ok(node)
+        }
+    }
+
+    fn parse_expression(&mut self) -> Result<Nodes, FilterError> {
+        let mut node = self.parse_unary()?;
+
+        loop {
+            match self.peek() {
+                Some(Tokens::And) => {
+                    self.position += 1;
+                    node = node.and(self.parse_unary()?);
+                }
+                Some(Tokens::Or) => {
+                    self.position += 1;
+                    node = node.or(self.parse_unary()?);
+                }
+                Some(Tokens::Word(word)) if word == "and" => {
+                    self.position += 1;
+                    node = node.and(self.parse_unary()?);
+                }
+                Some(Tokens::Word(word)) if word == "or" => {
+                    self.position += 1;
+                    node = node.or(self.parse_unary()?);
+                }
+                _ => break
+            }
+        }
+
+        Ok(node)
+    }
+
+    fn parse_unary(&mut self) -> Result<Nodes, FilterError> {
+        match self.peek() {
+            Some(Tokens::Not) => {
+                self.position += 1;
+                Ok(self.parse_unary()?.negate())
+            }
+            Some(Tokens::Word(word)) if word == "not" => {
+                self.position += 1;
+                Ok(self.parse_unary()?.negate())
+            }
+            _ => self.parse_primary()
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Nodes, FilterError> {
+        match self.peek() {
+            Some(Tokens::LeftParen) => {
+                self.position += 1;
+                let node = self.parse_expression()?;
+                self.expect(Tokens::RightParen)?;
+                Ok(node)
+            }
+            Some(Tokens::Word(word)) => {
+                let comparison = word == "len" || (PROTOCOLS.contains(&word.as_str()) &&
+                        matches!(self.tokens.get(self.position + 1), Some((_, Tokens::LeftBracket))));
+
+                match comparison {
+                    true => self.parse_comparison(),
+                    false => self.parse_primitive()
+                }
+            }
+            Some(_) => {
+                let (position, token) = self.tokens[self.position].clone();
+                Err(FilterError::UnexpectedToken {
+                    position,
+                    token: token.to_string()
+                })
+            }
+            None => Err(FilterError::UnexpectedEnd)
+        }
+    }
+
+    fn parse_primitive(&mut self) -> Result<Nodes, FilterError> {
+        let mut qualifiers = Qualifiers {
+            protocol: None,
+            direction: Directions::Any,
+            _type: "host".to_string()
+        };
+
+        if self.peek_word() == Some("vlan") {
+            self.position += 1;
+            return self.vlan();
+        }
+
+        let mut qualified = false;
+        let mut typed = false;
+
+        if let Some(word) = self.peek_word().filter(|word| PROTOCOLS.contains(word)) {
+            qualifiers.protocol = Some(word.to_string());
+            self.position += 1;
+            qualified = true;
+        }
+
+        if let Some(direction) = self.peek_word().and_then(Directions::from_name) {
+            qualifiers.direction = direction;
+            self.position += 1;
+            qualified = true;
+        }
+
+        if let Some(word) = self.peek_word().filter(|word| TYPES.contains(word)) {
+            qualifiers._type = word.to_string();
+            self.position += 1;
+            qualified = true;
+            typed = true;
+        }
+
+        if !qualified {
+            qualifiers = match &self.last {
+                Some(last) => last.clone(),
+                None => {
+                    let (position, value) = self.expect_word()?;
+                    return Err(FilterError::UnexpectedToken {
+                        position,
+                        token: value
+                    });
+                }
+            };
+
+        } else if !typed && qualifiers.direction == Directions::Any {
+            //JUST A PROTOCOL, "tcp" OR "ip6"
+            let position = self.tokens[self.position - 1].0;
+            return self.protocol(position, qualifiers.protocol.as_deref().unwrap());
+        }
+
+        let (position, value) = self.expect_word()?;
+        self.last = Some(qualifiers.clone());
+
+        match qualifiers._type.as_str() {
+            "host" => self.host(&qualifiers, position, &value),
+            "net" => {
+                let mask = match self.peek_word() {
+                    Some("mask") => {
+                        self.position += 1;
+                        Some(self.expect_word()?)
+                    }
+                    _ => None
+                };
+
+                self.net(&qualifiers, position, &value, mask)
+            }
+            "port" => self.port(&qualifiers, position, &value),
+            _ => self.proto(&qualifiers, position, &value)
+        }
+    }
+
+    fn parse_comparison(&mut self) -> Result<Nodes, FilterError> {
+        let (_, base) = self.expect_word()?;
+
+        let (guard, mut loads) = match base.as_str() {
+            "len" => (None, vec![BpfInstruction::statement(BPF_LD | BPF_W | BPF_LEN, 0)]),
+            _ => {
+                self.expect(Tokens::LeftBracket)?;
+                let offset = self.parse_value()?;
+
+                let size = match self.peek() {
+                    Some(Tokens::Colon) => {
+                        self.position += 1;
+                        let (position, size) = self.expect_word()?;
+                        match size.as_str() {
+                            "1" => BPF_B,
+                            "2" => BPF_H,
+                            "4" => BPF_W,
+                            _ => return Err(FilterError::InvalidValue {
+                                position,
+                                value: size
+                            })
+                        }
+                    }
+                    _ => BPF_B
+                };
+
+                self.expect(Tokens::RightBracket)?;
+
+                match base.as_str() {
+                    "ether" => (None, vec![BpfInstruction::statement(BPF_LD | size | BPF_ABS, offset)]),
+                    "ip" => (Some(self.ether_type(ETHER_TYPE_IPV4)), vec![BpfInstruction::statement(BPF_LD | size | BPF_ABS, ETHERNET_HEADER_SIZE + self.link + offset)]),
+                    "ip6" => (Some(self.ether_type(ETHER_TYPE_IPV6)), vec![BpfInstruction::statement(BPF_LD | size | BPF_ABS, ETHERNET_HEADER_SIZE + self.link + offset)]),
+                    "arp" => (Some(self.ether_type(ETHER_TYPE_ARP)), vec![BpfInstruction::statement(BPF_LD | size | BPF_ABS, ETHERNET_HEADER_SIZE + self.link + offset)]),
+                    "icmp6" => (Some(self.ip6_protocol(58)), vec![BpfInstruction::statement(BPF_LD | size | BPF_ABS, ETHERNET_HEADER_SIZE + self.link + 40 + offset)]),
+                    //TRANSPORT HEADERS SIT AFTER A VARIABLE LENGTH IPV4 HEADER, X IS LOADED WITH ITS LENGTH
+                    _ => {
+                        let protocol = match base.as_str() {
+                            "tcp" => 6,
+                            "udp" => 17,
+                            _ => 1
+                        };
+
+                        (Some(self.ip_protocol(protocol).and(self.not_fragment())), vec![
+                            BpfInstruction::statement(BPF_LDX | BPF_B | BPF_MSH, ETHERNET_HEADER_SIZE + self.link),
+                            BpfInstruction::statement(BPF_LD | size | BPF_IND, ETHERNET_HEADER_SIZE + self.link + offset)
+                        ])
+                    }
+                }
+            }
+        };
+
+        if let Some(Tokens::Ampersand) = self.peek() {
+            self.position += 1;
+            loads.push(BpfInstruction::statement(BPF_ALU | BPF_AND | BPF_K, self.parse_value()?));
+        }
+
+        let relation = match self.tokens.get(self.position) {
+            Some((_, token @ (Tokens::Equal | Tokens::NotEqual | Tokens::Less | Tokens::LessEqual | Tokens::Greater | Tokens::GreaterEqual))) => token.clone(),
+            Some((position, token)) => return Err(FilterError::UnexpectedToken {
+                position: *position,
+                token: token.to_string()
+            }),
+            None => return Err(FilterError::UnexpectedEnd)
+        };
+
+        self.position += 1;
+        let value = self.parse_value()?;
+
+        let test = match relation {
+            Tokens::Equal => Nodes::test(loads, BPF_JMP | BPF_JEQ | BPF_K, value),
+            Tokens::NotEqual => Nodes::test(loads, BPF_JMP | BPF_JEQ | BPF_K, value).negate(),
+            Tokens::Greater => Nodes::test(loads, BPF_JMP | BPF_JGT | BPF_K, value),
+            Tokens::GreaterEqual => Nodes::test(loads, BPF_JMP | BPF_JGE | BPF_K, value),
+            Tokens::Less => Nodes::test(loads, BPF_JMP | BPF_JGE | BPF_K, value).negate(),
+            _ => Nodes::test(loads, BPF_JMP | BPF_JGT | BPF_K, value).negate()
+        };
+
+        self.last = None;
+
+        Ok(match guard {
+            Some(guard) => guard.and(test),
+            None => test
+        })
+    }
+
+    fn parse_value(&mut self) -> Result<u32, FilterError> {
+        let (position, value) = self.expect_word()?;
+
+        let parsed = match value.as_str() {
+            "tcpflags" => Some(13),
+            "tcp-fin" => Some(0x01),
+            "tcp-syn" => Some(0x02),
+            "tcp-rst" => Some(0x04),
+            "tcp-push" => Some(0x08),
+            "tcp-ack" => Some(0x10),
+            "tcp-urg" => Some(0x20),
+            "icmptype" => Some(0),
+            "icmpcode" => Some(1),
+            "icmp-echoreply" => Some(0),
+            "icmp-unreach" => Some(3),
+            "icmp-redirect" => Some(5),
+            "icmp-echo" => Some(8),
+            "icmp-timxceed" => Some(11),
+            _ => Self::parse_number(&value)
+        };
+
+        parsed.ok_or(FilterError::InvalidValue {
+            position,
+            value
+        })
+    }
+
+    fn parse_number(value: &str) -> Option<u32> {
+        match value.strip_prefix("0x") {
+            Some(hex) => u32::from_str_radix(hex, 16).ok(),
+            None => value.parse().ok()
+        }
+    }
+
+    fn protocol(&self, position: usize, protocol: &str) -> Result<Nodes, FilterError> {
+        Ok(match protocol {
+            "ip" => self.ether_type(ETHER_TYPE_IPV4),
+            "ip6" => self.ether_type(ETHER_TYPE_IPV6),
+            "arp" => self.ether_type(ETHER_TYPE_ARP),
+            "tcp" => self.ip_protocol(6).or(self.ip6_protocol(6)),
+            "udp" => self.ip_protocol(17).or(self.ip6_protocol(17)),
+            "icmp" => self.ip_protocol(1),
+            "icmp6" => self.ip6_protocol(58),
+            _ => return Err(FilterError::InvalidQualifier {
+                position,
+                qualifier: protocol.to_string()
+            })
+        })
+    }
+
+    fn host(&self, qualifiers: &Qualifiers, position: usize, value: &str) -> Result<Nodes, FilterError> {
+        let invalid_value = || FilterError::InvalidValue {
+            position,
+            value: value.to_string()
+        };
+
+        let invalid_qualifier = || FilterError::InvalidQualifier {
+            position,
+            qualifier: qualifiers.protocol.clone().unwrap_or_default()
+        };
+
+        if qualifiers.protocol.as_deref() == Some("ether") {
+            let address = Self::parse_mac(value).ok_or_else(invalid_value)?;
+            return Ok(Self::direction(qualifiers.direction, |source| {
+                let offset = match source {
+                    true => 6,
+                    false => 0
+                };
+
+                Self::bytes_equal(offset, &address)
+            }));
+        }
+
+        match value.parse::<IpAddr>().map_err(|_| invalid_value())? {
+            IpAddr::V4(address) => {
+                let ip = self.ether_type(ETHER_TYPE_IPV4).and(Self::direction(qualifiers.direction, |source| {
+                    self.ipv4_equal(if source { 26 } else { 30 }, address, u32::MAX)
+                }));
+
+                let arp = self.ether_type(ETHER_TYPE_ARP).and(Self::direction(qualifiers.direction, |source| {
+                    self.ipv4_equal(if source { 28 } else { 38 }, address, u32::MAX)
+                }));
+
+                match qualifiers.protocol.as_deref() {
+                    None => Ok(ip.or(arp)),
+                    Some("ip") => Ok(ip),
+                    Some("arp") => Ok(arp),
+                    _ => Err(invalid_qualifier())
+                }
+            }
+            IpAddr::V6(address) => match qualifiers.protocol.as_deref() {
+                None | Some("ip6") => Ok(self.ether_type(ETHER_TYPE_IPV6).and(Self::direction(qualifiers.direction, |source| {
+                    self.ipv6_equal(if source { 22 } else { 38 }, address, 128)
+                }))),
+                _ => Err(invalid_qualifier())
+            }
+        }
+    }
+
+    fn net(&self, qualifiers: &Qualifiers, position: usize, value: &str, mask: Option<(usize, String)>) -> Result<Nodes, FilterError> {
+        let invalid_value = || FilterError::InvalidValue {
+            position,
+            value: value.to_string()
+        };
+
+        let invalid_qualifier = || FilterError::InvalidQualifier {
+            position,
+            qualifier: qualifiers.protocol.clone().unwrap_or_default()
+        };
+
+        let (address, prefix) = match value.split_once('/') {
+            Some((address, prefix)) => (address.parse::<IpAddr>().map_err(|_| invalid_value())?, Some(prefix.parse::<u32>().map_err(|_| invalid_value())?)),
+            None => (value.parse::<IpAddr>().map_err(|_| invalid_value())?, None)
+        };
+
+        match address {
+            IpAddr::V4(address) => {
+                let mask = match (prefix, mask) {
+                    (Some(_), Some((position, mask))) => return Err(FilterError::InvalidValue {
+                        position,
+                        value: mask
+                    }),
+                    (Some(prefix), None) if prefix <= 32 => u32::MAX.checked_shl(32 - prefix).unwrap_or(0),
+                    (Some(_), None) => return Err(invalid_value()),
+                    (None, Some((position, mask))) => u32::from(mask.parse::<Ipv4Addr>().map_err(|_| FilterError::InvalidValue {
+                        position,
+                        value: mask
+                    })?),
+                    (None, None) => u32::MAX
+                };
+
+                let ip = self.ether_type(ETHER_TYPE_IPV4).and(Self::direction(qualifiers.direction, |source| {
+                    self.ipv4_equal(if source { 26 } else { 30 }, address, mask)
+                }));
+
+                let arp = self.ether_type(ETHER_TYPE_ARP).and(Self::direction(qualifiers.direction, |source| {
+                    self.ipv4_equal(if source { 28 } else { 38 }, address, mask)
+                }));
+
+                match qualifiers.protocol.as_deref() {
+                    None => Ok(ip.or(arp)),
+                    Some("ip") => Ok(ip),
+                    Some("arp") => Ok(arp),
+                    _ => Err(invalid_qualifier())
+                }
+            }
+            IpAddr::V6(address) => {
+                let prefix = match (prefix, mask) {
+                    (_, Some((position, mask))) => return Err(FilterError::InvalidValue {
+                        position,
+                        value: mask
+                    }),
+                    (Some(prefix), None) if prefix <= 128 => prefix,
+                    (Some(_), None) => return Err(invalid_value()),
+                    (None, None) => 128
+                };
+
+                match qualifiers.protocol.as_deref() {
+                    None | Some("ip6") => Ok(self.ether_type(ETHER_TYPE_IPV6).and(Self::direction(qualifiers.direction, |source| {
+                        self.ipv6_equal(if source { 22 } else { 38 }, address, prefix)
+                    }))),
+                    _ => Err(invalid_qualifier())
+                }
+            }
+        }
+    }
+
+    fn port(&self, qualifiers: &Qualifiers, position: usize, value: &str) -> Result<Nodes, FilterError> {
+        let port = value.parse::<u16>().map_err(|_| FilterError::InvalidValue {
+            position,
+            value: value.to_string()
+        })? as u32;
+
+        let protocols = match qualifiers.protocol.as_deref() {
+            None => vec![6, 17],
+            Some("tcp") => vec![6],
+            Some("udp") => vec![17],
+            Some(protocol) => return Err(FilterError::InvalidQualifier {
+                position,
+                qualifier: protocol.to_string()
+            })
+        };
+
+        let ipv4 = protocols.iter()
+            .map(|protocol| Nodes::test(vec![BpfInstruction::statement(BPF_LD | BPF_B | BPF_ABS, 23 + self.link)], BPF_JMP | BPF_JEQ | BPF_K, *protocol))
+            .reduce(Nodes::or)
+            .unwrap();
+
+        let ipv6 = protocols.iter()
+            .map(|protocol| Nodes::test(vec![BpfInstruction::statement(BPF_LD | BPF_B | BPF_ABS, 20 + self.link)], BPF_JMP | BPF_JEQ | BPF_K, *protocol))
+            .reduce(Nodes::or)
+            .unwrap();
+
+        let ipv4 = self.ether_type(ETHER_TYPE_IPV4).and(ipv4).and(self.not_fragment()).and(Self::direction(qualifiers.direction, |source| {
+            Nodes::test(vec![
+                BpfInstruction::statement(BPF_LDX | BPF_B | BPF_MSH, ETHERNET_HEADER_SIZE + self.link),
+                BpfInstruction::statement(BPF_LD | BPF_H | BPF_IND, ETHERNET_HEADER_SIZE + self.link + if source { 0 } else { 2 })
+            ], BPF_JMP | BPF_JEQ | BPF_K, port)
+        }));
+
+        let ipv6 = self.ether_type(ETHER_TYPE_IPV6).and(ipv6).and(Self::direction(qualifiers.direction, |source| {
+            Nodes::test(vec![
+                BpfInstruction::statement(BPF_LD | BPF_H | BPF_ABS, ETHERNET_HEADER_SIZE + self.link + 40 + if source { 0 } else { 2 })
+            ], BPF_JMP | BPF_JEQ | BPF_K, port)
+        }));
+
+        Ok(ipv4.or(ipv6))
+    }
+
+    fn proto(&self, qualifiers: &Qualifiers, position: usize, value: &str) -> Result<Nodes, FilterError> {
+        if qualifiers.direction != Directions::Any {
+            return Err(FilterError::InvalidQualifier {
+                position,
+                qualifier: qualifiers.direction.to_string()
+            });
+        }
+
+        let name = value.trim_start_matches('\\');
+        let invalid_value = || FilterError::InvalidValue {
+            position,
+            value: value.to_string()
+        };
+
+        match qualifiers.protocol.as_deref() {
+            Some("ether") => {
+                let ether_type = match name {
+                    "ip" => ETHER_TYPE_IPV4 as u32,
+                    "ip6" => ETHER_TYPE_IPV6 as u32,
+                    "arp" => ETHER_TYPE_ARP as u32,
+                    _ => Self::parse_number(name).filter(|code| *code <= u16::MAX as u32).ok_or_else(invalid_value)?
+                };
+
+                Ok(self.ether_type(ether_type as u16))
+            }
+            protocol @ (None | Some("ip") | Some("ip6")) => {
+                let code = match name {
+                    "icmp" => 1,
+                    "tcp" => 6,
+                    "udp" => 17,
+                    "icmp6" => 58,
+                    _ => Self::parse_number(name).filter(|code| *code <= u8::MAX as u32).ok_or_else(invalid_value)?
+                } as u8;
+
+                Ok(match protocol {
+                    Some("ip") => self.ip_protocol(code),
+                    Some("ip6") => self.ip6_protocol(code),
+                    _ => self.ip_protocol(code).or(self.ip6_protocol(code))
+                })
+            }
+            Some(protocol) => Err(FilterError::InvalidQualifier {
+                position,
+                qualifier: protocol.to_string()
+            })
+        }
+    }
+
+    fn vlan(&mut self) -> Result<Nodes, FilterError> {
+        let tagged = ETHER_TYPE_VLANS.iter()
+            .map(|ether_type| self.ether_type(*ether_type))
+            .reduce(Nodes::or)
+            .unwrap();
+
+        //THE ID IS OPTIONAL, "vlan and udp" ONLY CHECKS FOR A TAG
+        let node = match self.peek_word().and_then(Self::parse_number) {
+            Some(id) => {
+                let (position, value) = self.expect_word()?;
+
+                if id > 0xfff {
+                    return Err(FilterError::InvalidValue {
+                        position,
+                        value
+                    });
+                }
+
+                tagged.and(Nodes::test(vec![
+                    BpfInstruction::statement(BPF_LD | BPF_H | BPF_ABS, ETHERNET_HEADER_SIZE + self.link),
+                    BpfInstruction::statement(BPF_ALU | BPF_AND | BPF_K, 0xfff)
+                ], BPF_JMP | BPF_JEQ | BPF_K, id))
+            }
+            None => tagged
+        };
+
+        self.link += VLAN_TAG_SIZE;
+        self.last = None;
+        Ok(node)
+    }
+
+    fn direction<F: Fn(bool) -> Nodes>(direction: Directions, test: F) -> Nodes {
+        match direction {
+            Directions::Source => test(true),
+            Directions::Destination => test(false),
+            Directions::Any => test(true).or(test(false))
+        }
+    }
+
+    fn ether_type(&self, ether_type: u16) -> Nodes {
+        Nodes::test(vec![BpfInstruction::statement(BPF_LD | BPF_H | BPF_ABS, 12 + self.link)], BPF_JMP | BPF_JEQ | BPF_K, ether_type as u32)
+    }
+
+    fn ip_protocol(&self, protocol: u8) -> Nodes {
+        self.ether_type(ETHER_TYPE_IPV4).and(Nodes::test(vec![BpfInstruction::statement(BPF_LD | BPF_B | BPF_ABS, 23 + self.link)], BPF_JMP | BPF_JEQ | BPF_K, protocol as u32))
+    }
+
+    fn ip6_protocol(&self, protocol: u8) -> Nodes {
+        self.ether_type(ETHER_TYPE_IPV6).and(Nodes::test(vec![BpfInstruction::statement(BPF_LD | BPF_B | BPF_ABS, 20 + self.link)], BPF_JMP | BPF_JEQ | BPF_K, protocol as u32))
+    }
+
+    fn not_fragment(&self) -> Nodes {
+        Nodes::test(vec![BpfInstruction::statement(BPF_LD | BPF_H | BPF_ABS, 20 + self.link)], BPF_JMP | BPF_JSET | BPF_K, 0x1fff).negate()
+    }
+
+    fn ipv4_equal(&self, offset: u32, address: Ipv4Addr, mask: u32) -> Nodes {
+        let mut loads = vec![BpfInstruction::statement(BPF_LD | BPF_W | BPF_ABS, offset + self.link)];
+
+        match mask {
+            0 => return Nodes::True,
+            u32::MAX => {}
+            _ => loads.push(BpfInstruction::statement(BPF_ALU | BPF_AND | BPF_K, mask))
+        }
+
+        Nodes::test(loads, BPF_JMP | BPF_JEQ | BPF_K, u32::from(address) & mask)
+    }
+
+    fn ipv6_equal(&self, offset: u32, address: Ipv6Addr, prefix: u32) -> Nodes {
+        let octets = address.octets();
+
+        (0..4)
+            .map(|i| {
+                let word = u32::from_be_bytes(octets[i * 4..i * 4 + 4].try_into().unwrap());
+                let bits = prefix.saturating_sub(i as u32 * 32).min(32);
+                let mask = u32::MAX.checked_shl(32 - bits).unwrap_or(0);
+                self.ipv4_equal(offset + i as u32 * 4, Ipv4Addr::from(word), mask)
+            })
+            .filter(|node| *node != Nodes::True)
+            .reduce(Nodes::and)
+            .unwrap_or(Nodes::True)
+    }
+
+    fn bytes_equal(offset: u32, bytes: &[u8; 6]) -> Nodes {
+        Nodes::test(vec![BpfInstruction::statement(BPF_LD | BPF_W | BPF_ABS, offset + 2)], BPF_JMP | BPF_JEQ | BPF_K, u32::from_be_bytes([bytes[2], bytes[3], bytes[4], bytes[5]]))
+            .and(Nodes::test(vec![BpfInstruction::statement(BPF_LD | BPF_H | BPF_ABS, offset)], BPF_JMP | BPF_JEQ | BPF_K, u16::from_be_bytes([bytes[0], bytes[1]]) as u32))
+    }
+
+    fn parse_mac(value: &str) -> Option<[u8; 6]> {
+        let parts = value.split([':', '-']).collect::<Vec<_>>();
+        if parts.len() != 6 {
+            return None;
+        }
+
+        let mut address = [0; 6];
+        for (i, part) in parts.iter().enumerate() {
+            address[i] = u8::from_str_radix(part, 16).ok()?;
+        }
+
+        Some(address)
+    }
+
+    fn peek(&self) -> Option<&Tokens> {
+        self.tokens.get(self.position).map(|(_, token)| token)
+    }
+
+    fn peek_word(&self) -> Option<&str> {
+        match self.peek() {
+            Some(Tokens::Word(word)) => Some(word.as_str()),
+            _ => None
+        }
+    }
+
+    fn expect(&mut self, expected: Tokens) -> Result<(), FilterError> {
+        match self.tokens.get(self.position) {
+            Some((_, token)) if *token == expected => {
+                self.position += 1;
+                Ok(())
+            }
+            Some((position, token)) => Err(FilterError::UnexpectedToken {
+                position: *position,
+                token: token.to_string()
+            }),
+            None => Err(FilterError::UnexpectedEnd)
+        }
+    }
+
+    fn expect_word(&mut self) -> Result<(usize, String), FilterError> {
+        match self.tokens.get(self.position) {
+            Some((position, Tokens::Word(word))) => {
+                let word = (*position, word.clone());
+                self.position += 1;
+                Ok(word)
+            }
+            Some((position, token)) => Err(FilterError::UnexpectedToken {
+                position: *position,
+                token: token.to_string()
+            }),
+            None => Err(FilterError::UnexpectedEnd)
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//INSTRUCTION CLASSES
+pub const BPF_LD: u16 = 0x00;
+pub const BPF_LDX: u16 = 0x01;
+pub const BPF_ST: u16 = 0x02;
+pub const BPF_STX: u16 = 0x03;
+pub const BPF_ALU: u16 = 0x04;
+pub const BPF_JMP: u16 = 0x05;
+pub const BPF_RET: u16 = 0x06;
+pub const BPF_MISC: u16 = 0x07;
+
+//LOAD SIZES
+pub const BPF_W: u16 = 0x00;
+pub const BPF_H: u16 = 0x08;
+pub const BPF_B: u16 = 0x10;
+
+//LOAD MODES
+pub const BPF_IMM: u16 = 0x00;
+pub const BPF_ABS: u16 = 0x20;
+pub const BPF_IND: u16 = 0x40;
+pub const BPF_MEM: u16 = 0x60;
+pub const BPF_LEN: u16 = 0x80;
+pub const BPF_MSH: u16 = 0xa0;
+
+//ALU OPERATIONS
+pub const BPF_ADD: u16 = 0x00;
+pub const BPF_SUB: u16 = 0x10;
+pub const BPF_MUL: u16 = 0x20;
+pub const BPF_DIV: u16 = 0x30;
+pub const BPF_OR: u16 = 0x40;
+pub const BPF_AND: u16 = 0x50;
+pub const BPF_LSH: u16 = 0x60;
+pub const BPF_RSH: u16 = 0x70;
+pub const BPF_NEG: u16 = 0x80;
+pub const BPF_MOD: u16 = 0x90;
+pub const BPF_XOR: u16 = 0xa0;
+
+//JUMP CONDITIONS
+pub const BPF_JA: u16 = 0x00;
+pub const BPF_JEQ: u16 = 0x10;
+pub const BPF_JGT: u16 = 0x20;
+pub const BPF_JGE: u16 = 0x30;
+pub const BPF_JSET: u16 = 0x40;
+
+//OPERAND SOURCES
+pub const BPF_K: u16 = 0x00;
+pub const BPF_X: u16 = 0x08;
+pub const BPF_A: u16 = 0x10;
+
+//MISC OPERATIONS
+pub const BPF_TAX: u16 = 0x00;
+pub const BPF_TXA: u16 = 0x80;
+
+pub const BPF_MAXINSNS: usize = 4096;
+pub const BPF_MEMWORDS: usize = 16;
+
+//LAID OUT EXACTLY LIKE struct sock_filter SO A SLICE CAN BE HANDED TO THE KERNEL
+#[repr(C)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct BpfInstruction {
+    code: u16,
+    jt: u8,
+    jf: u8,
+    k: u32
+}
+
+impl BpfInstruction {
+
+    pub fn new(code: u16, jt: u8, jf: u8, k: u32) -> Self {
+        Self {
+            code,
+            jt,
+            jf,
+            k
+        }
+    }
+
+    pub fn statement(code: u16, k: u32) -> Self {
+        Self::new(code, 0, 0, k)
+    }
+
+    pub fn jump(code: u16, k: u32, jt: u8, jf: u8) -> Self {
+        Self::new(code, jt, jf, k)
+    }
+
+    pub fn get_code(&self) -> u16 {
+        self.code
+    }
+
+    pub fn get_class(&self) -> u16 {
+        self.code & 0x07
+    }
+
+    pub fn get_jt(&self) -> u8 {
+        self.jt
+    }
+
+    pub fn get_jf(&self) -> u8 {
+        self.jf
+    }
+
+    pub fn get_k(&self) -> u32 {
+        self.k
+    }
+}
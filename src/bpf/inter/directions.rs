@@ -0,0 +1,26 @@
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Directions {
+    Source,
+    Destination,
+    Any
+}
+
+impl Directions {
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "src" => Some(Self::Source),
+            "dst" => Some(Self::Destination),
+            _ => None
+        }
+    }
+
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        match self {
+            Self::Source => "src",
+            Self::Destination => "dst",
+            Self::Any => "src or dst"
+        }.to_string()
+    }
+}
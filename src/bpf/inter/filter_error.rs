@@ -0,0 +1,111 @@
+use std::{fmt, io};
+use std::error::Error;
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum FilterError {
+    UnexpectedToken {
+        position: usize,
+        token: String
+    },
+    UnexpectedEnd,
+    InvalidValue {
+        position: usize,
+        value: String
+    },
+    InvalidQualifier {
+        position: usize,
+        qualifier: String
+    },
+    TooLong {
+        length: usize
+    },
+    Empty,
+    InvalidInstruction {
+        index: usize,
+        code: u16
+    },
+    BadJump {
+        index: usize
+    },
+    BadMemory {
+        index: usize
+    },
+    DivisionByZero {
+        index: usize
+    },
+    MissingReturn
+}
+
+impl FilterError {
+
+    //CHARACTER POSITION IN THE EXPRESSION, NONE WHEN IT RAN OUT OR THE PROGRAM ITSELF IS THE PROBLEM
+    pub fn get_position(&self) -> Option<usize> {
+        match self {
+            Self::UnexpectedToken { position, .. } |
+            Self::InvalidValue { position, .. } |
+            Self::InvalidQualifier { position, .. } => Some(*position),
+            _ => None
+        }
+    }
+
+    //INSTRUCTION THE VERIFIER REJECTED
+    pub fn get_index(&self) -> Option<usize> {
+        match self {
+            Self::InvalidInstruction { index, .. } |
+            Self::BadJump { index } |
+            Self::BadMemory { index } |
+            Self::DivisionByZero { index } => Some(*index),
+            _ => None
+        }
+    }
+}
+
+impl fmt::Display for FilterError {
+
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedToken { position, token } => {
+                write!(f, "unexpected '{}' at position {}", token, position)
+            }
+            Self::UnexpectedEnd => {
+                write!(f, "unexpected end of filter expression")
+            }
+            Self::InvalidValue { position, value } => {
+                write!(f, "invalid value '{}' at position {}", value, position)
+            }
+            Self::InvalidQualifier { position, qualifier } => {
+                write!(f, "'{}' can't be used here at position {}", qualifier, position)
+            }
+            Self::TooLong { length } => {
+                write!(f, "compiled filter has {} instructions, the kernel allows at most 4096", length)
+            }
+            Self::Empty => {
+                write!(f, "filter program has no instructions")
+            }
+            Self::InvalidInstruction { index, code } => {
+                write!(f, "invalid instruction {:#06x} at {}", code, index)
+            }
+            Self::BadJump { index } => {
+                write!(f, "jump at {} lands outside the program", index)
+            }
+            Self::BadMemory { index } => {
+                write!(f, "scratch memory index out of range at {}", index)
+            }
+            Self::DivisionByZero { index } => {
+                write!(f, "division by constant zero at {}", index)
+            }
+            Self::MissingReturn => {
+                write!(f, "filter program doesn't end with a return")
+            }
+        }
+    }
+}
+
+impl Error for FilterError {}
+
+impl From<FilterError> for io::Error {
+
+    fn from(error: FilterError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidInput, error)
+    }
+}
@@ -0,0 +1,5 @@
+pub mod bpf_instruction;
+pub mod directions;
+pub mod filter_error;
+pub mod nodes;
+pub mod tokens;
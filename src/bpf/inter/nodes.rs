@@ -0,0 +1,42 @@
+use crate::bpf::inter::bpf_instruction::BpfInstruction;
+
+/*
+Filter expressions are parsed into a tree of tests.
+A test runs its loads then takes a single conditional jump, everything else is how the tests are wired together.
+*/
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Nodes {
+    And(Box<Nodes>, Box<Nodes>),
+    Or(Box<Nodes>, Box<Nodes>),
+    Not(Box<Nodes>),
+    Test {
+        loads: Vec<BpfInstruction>,
+        jump: u16,
+        k: u32
+    },
+    True,
+    False
+}
+
+impl Nodes {
+
+    pub fn test(loads: Vec<BpfInstruction>, jump: u16, k: u32) -> Self {
+        Self::Test {
+            loads,
+            jump,
+            k
+        }
+    }
+
+    pub fn and(self, other: Nodes) -> Self {
+        Self::And(Box::new(self), Box::new(other))
+    }
+
+    pub fn or(self, other: Nodes) -> Self {
+        Self::Or(Box::new(self), Box::new(other))
+    }
+
+    pub fn negate(self) -> Self {
+        Self::Not(Box::new(self))
+    }
+}
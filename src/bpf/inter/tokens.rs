@@ -0,0 +1,113 @@
+use crate::bpf::inter::filter_error::FilterError;
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Tokens {
+    Word(String),
+    LeftParen,
+    RightParen,
+    LeftBracket,
+    RightBracket,
+    Colon,
+    Ampersand,
+    And,
+    Or,
+    Not,
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual
+}
+
+impl Tokens {
+
+    //RETURNS EACH TOKEN WITH ITS CHARACTER POSITION
+    pub fn tokenize(expression: &str) -> Result<Vec<(usize, Self)>, FilterError> {
+        let chars = expression.chars().collect::<Vec<_>>();
+        let mut tokens = Vec::new();
+        let mut brackets = 0;
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+            let next = chars.get(i + 1).copied();
+
+            let (token, width) = match (c, next) {
+                (c, _) if c.is_whitespace() => {
+                    i += 1;
+                    continue;
+                }
+                ('(', _) => (Self::LeftParen, 1),
+                (')', _) => (Self::RightParen, 1),
+                ('[', _) => {
+                    brackets += 1;
+                    (Self::LeftBracket, 1)
+                }
+                (']', _) => {
+                    brackets -= 1;
+                    (Self::RightBracket, 1)
+                }
+                ('&', Some('&')) => (Self::And, 2),
+                ('&', _) => (Self::Ampersand, 1),
+                ('|', Some('|')) => (Self::Or, 2),
+                ('!', Some('=')) => (Self::NotEqual, 2),
+                ('!', _) => (Self::Not, 1),
+                ('=', Some('=')) => (Self::Equal, 2),
+                ('=', _) => (Self::Equal, 1),
+                ('<', Some('=')) => (Self::LessEqual, 2),
+                ('<', _) => (Self::Less, 1),
+                ('>', Some('=')) => (Self::GreaterEqual, 2),
+                ('>', _) => (Self::Greater, 1),
+                //INSIDE BRACKETS A COLON SPLITS OFFSET AND SIZE, OUTSIDE IT'S PART OF AN ADDRESS
+                (':', _) if brackets > 0 => (Self::Colon, 1),
+                (c, _) if Self::is_word_char(c) => {
+                    let start = i;
+                    while i < chars.len() && Self::is_word_char(chars[i]) && !(chars[i] == ':' && brackets > 0) {
+                        i += 1;
+                    }
+
+                    tokens.push((start, Self::Word(chars[start..i].iter().collect())));
+                    continue;
+                }
+                (c, _) => {
+                    return Err(FilterError::UnexpectedToken {
+                        position: i,
+                        token: c.to_string()
+                    });
+                }
+            };
+
+            tokens.push((i, token));
+            i += width;
+        }
+
+        Ok(tokens)
+    }
+
+    fn is_word_char(c: char) -> bool {
+        c.is_ascii_alphanumeric() || matches!(c, '.' | ':' | '/' | '-' | '_' | '\\')
+    }
+
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        match self {
+            Self::Word(word) => word,
+            Self::LeftParen => "(",
+            Self::RightParen => ")",
+            Self::LeftBracket => "[",
+            Self::RightBracket => "]",
+            Self::Colon => ":",
+            Self::Ampersand => "&",
+            Self::And => "&&",
+            Self::Or => "||",
+            Self::Not => "!",
+            Self::Equal => "=",
+            Self::NotEqual => "!=",
+            Self::Less => "<",
+            Self::LessEqual => "<=",
+            Self::Greater => ">",
+            Self::GreaterEqual => ">="
+        }.to_string()
+    }
+}
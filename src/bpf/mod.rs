@@ -0,0 +1,4 @@
+pub mod inter;
+pub mod bpf_machine;
+pub mod bpf_program;
+pub mod filter_parser;
@@ -0,0 +1,43 @@
+use std::io;
+use crate::capture::inter::tx_statuses::TxStatuses;
+
+/*
+What TxRing::send_batch got through before it stopped. The statuses cover every packet that made it into the ring,
+in order, the error is whatever stopped the batch so a failure part way can't be told apart from success without looking.
+*/
+#[must_use]
+#[derive(Debug)]
+pub struct BatchResult {
+    statuses: Vec<TxStatuses>,
+    error: Option<io::Error>
+}
+
+impl BatchResult {
+
+    pub fn new(statuses: Vec<TxStatuses>, error: Option<io::Error>) -> Self {
+        Self {
+            statuses,
+            error
+        }
+    }
+
+    pub fn get_statuses(&self) -> &Vec<TxStatuses> {
+        &self.statuses
+    }
+
+    pub fn get_error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+
+    //THE STATUSES OF A BATCH THAT STOPPED PART WAY ARE LOST, USE THE GETTERS TO KEEP THEM
+    pub fn into_result(self) -> io::Result<Vec<TxStatuses>> {
+        match self.error {
+            Some(e) => Err(e),
+            None => Ok(self.statuses)
+        }
+    }
+}
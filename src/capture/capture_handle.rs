@@ -0,0 +1,88 @@
+use std::{io, thread};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use crate::capture::Capture;
+use crate::packet::packet::Packet;
+
+//HOW LONG THE CAPTURE THREAD WAITS FOR A PACKET BEFORE LOOKING AT THE STOP FLAG AGAIN
+pub const STOP_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/*
+Stops the capture thread started by spawn_capture and hands the capture back.
+Dropping the handle stops the thread too, the capture is closed with it.
+*/
+#[derive(Debug)]
+pub struct CaptureHandle {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<Capture>>
+}
+
+impl CaptureHandle {
+
+    pub(crate) fn spawn(mut capture: Capture, sender: Sender<io::Result<Packet>>) -> io::Result<Self> {
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+
+        let thread = thread::Builder::new().name(format!("capture-{}", capture.get_device().get_name())).spawn(move || {
+            while !thread_stop.load(Ordering::Acquire) {
+                match capture.wait(Some(STOP_CHECK_INTERVAL)) {
+                    Ok(true) => {}
+                    Ok(false) => continue,
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(e) => {
+                        sender.send(Err(e)).ok();
+                        break;
+                    }
+                }
+
+                match capture.next_packet() {
+                    Err(e) if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut | io::ErrorKind::Interrupted) => {}
+                    res => {
+                        //DECODE ERRORS ARE PASSED ON AND THE LOOP KEEPS GOING, ANYTHING ELSE ENDS IT
+                        let fatal = matches!(&res, Err(e) if e.kind() != io::ErrorKind::InvalidData);
+
+                        if sender.send(res).is_err() || fatal {
+                            break;
+                        }
+                    }
+                }
+            }
+
+            capture
+        })?;
+
+        Ok(Self {
+            stop,
+            thread: Some(thread)
+        })
+    }
+
+    //TRUE ONCE THE THREAD HAS ENDED, EITHER AFTER AN ERROR OR BECAUSE THE RECEIVER WAS DROPPED
+    pub fn is_finished(&self) -> bool {
+        self.thread.as_ref().is_none_or(|thread| thread.is_finished())
+    }
+
+    //WAITS UP TO STOP_CHECK_INTERVAL FOR THE THREAD TO NOTICE
+    pub fn stop(mut self) -> Capture {
+        self.stop.store(true, Ordering::Release);
+
+        match self.thread.take().unwrap().join() {
+            Ok(capture) => capture,
+            Err(e) => std::panic::resume_unwind(e)
+        }
+    }
+}
+
+impl Drop for CaptureHandle {
+
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+
+        if let Some(thread) = self.thread.take() {
+            thread.join().ok();
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::io;
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, RawFd};
+#[cfg(feature = "async")]
+use std::task::{Context, Poll};
+use std::time::Duration;
+use crate::capture::Capture;
+use crate::capture::capture_stats::CaptureStats;
+use crate::capture::frame_info::FrameInfo;
+#[cfg(feature = "async")]
+use crate::capture::next_packet::NextPacket;
+use crate::devices::Device;
+use crate::packet::packet::Packet;
+
+//RECEIVING HALF OF A SPLIT CAPTURE, CLOSES ITS DESCRIPTOR WHEN DROPPED
+#[derive(Debug)]
+pub struct CaptureReader {
+    capture: Capture
+}
+
+impl CaptureReader {
+
+    pub fn new(capture: Capture) -> Self {
+        Self {
+            capture
+        }
+    }
+
+    pub fn get_device(&self) -> &Device {
+        self.capture.get_device()
+    }
+
+    pub fn set_read_timeout(&mut self, timeout: Option<Duration>) -> io::Result<()> {
+        self.capture.set_read_timeout(timeout)
+    }
+
+    pub fn get_read_timeout(&self) -> io::Result<Option<Duration>> {
+        self.capture.get_read_timeout()
+    }
+
+    //O_NONBLOCK SITS ON THE SHARED SOCKET SO THE SENDER STOPS BLOCKING AS WELL
+    pub fn set_nonblocking(&mut self, nonblocking: bool) -> io::Result<()> {
+        self.capture.set_nonblocking(nonblocking)
+    }
+
+    pub fn is_nonblocking(&self) -> io::Result<bool> {
+        self.capture.is_nonblocking()
+    }
+
+    pub fn wait(&self, timeout: Option<Duration>) -> io::Result<bool> {
+        self.capture.wait(timeout)
+    }
+
+    pub fn next_packet(&mut self) -> io::Result<Packet> {
+        self.capture.next_packet()
+    }
+
+    #[cfg(feature = "async")]
+    pub fn poll_next_packet(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<Packet>> {
+        self.capture.poll_next_packet(cx)
+    }
+
+    #[cfg(feature = "async")]
+    pub fn next_packet_async(&mut self) -> NextPacket<'_> {
+        self.capture.next_packet_async()
+    }
+
+    pub fn next_frame<'a>(&mut self, buffer: &'a mut [u8]) -> io::Result<(FrameInfo, &'a [u8])> {
+        self.capture.next_frame(buffer)
+    }
+
+    pub fn stats(&mut self) -> io::Result<CaptureStats> {
+        self.capture.stats()
+    }
+
+    pub fn into_capture(self) -> Capture {
+        self.capture
+    }
+}
+
+impl AsRawFd for CaptureReader {
+
+    fn as_raw_fd(&self) -> RawFd {
+        self.capture.as_raw_fd()
+    }
+}
+
+impl AsFd for CaptureReader {
+
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.capture.as_fd()
+    }
+}
@@ -0,0 +1,54 @@
+use std::io;
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, RawFd};
+use crate::capture::Capture;
+use crate::devices::Device;
+use crate::packet::packet::Packet;
+
+//SENDING HALF OF A SPLIT CAPTURE, SENDS ONLY NEED &self SO IT CAN BE SHARED BETWEEN THREADS
+#[derive(Debug)]
+pub struct CaptureSender {
+    capture: Capture
+}
+
+impl CaptureSender {
+
+    pub fn new(capture: Capture) -> Self {
+        Self {
+            capture
+        }
+    }
+
+    pub fn get_device(&self) -> &Device {
+        self.capture.get_device()
+    }
+
+    pub fn get_if_index(&self) -> io::Result<i32> {
+        self.capture.get_if_index()
+    }
+
+    pub fn send_packet(&self, packet: Packet) -> io::Result<usize> {
+        self.capture.send_packet(packet)
+    }
+
+    pub fn send_bytes(&self, data: &[u8]) -> io::Result<usize> {
+        self.capture.send_bytes(data)
+    }
+
+    pub fn try_clone(&self) -> io::Result<Self> {
+        Ok(Self::new(self.capture.try_clone()?))
+    }
+}
+
+impl AsRawFd for CaptureSender {
+
+    fn as_raw_fd(&self) -> RawFd {
+        self.capture.as_raw_fd()
+    }
+}
+
+impl AsFd for CaptureSender {
+
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.capture.as_fd()
+    }
+}
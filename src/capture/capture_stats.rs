@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+use crate::packet::inter::decode_error::DecodeError;
+use crate::pcapng::blocks::interface_statistics_block::{InterfaceStatisticsBlock, ISB_IF_DROP, ISB_IF_RECV, ISB_OS_DROP};
+
+/*
+Same counters as pcap_stats, received includes what the kernel dropped.
+Decode errors are counted by the library per layer, they were received fine but couldn't be parsed.
+*/
+#[derive(Clone, Default, Debug)]
+pub struct CaptureStats {
+    received: u64,
+    dropped: u64,
+    interface_dropped: u64,
+    freeze_count: u64,
+    decode_errors: HashMap<&'static str, u64>
+}
+
+impl CaptureStats {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get_received(&self) -> u64 {
+        self.received
+    }
+
+    pub fn get_dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    pub fn get_interface_dropped(&self) -> u64 {
+        self.interface_dropped
+    }
+
+    //HOW MANY TIMES THE TPACKET_V3 RING FROZE BECAUSE EVERY BLOCK WAS STILL HELD BY US
+    pub fn get_freeze_count(&self) -> u64 {
+        self.freeze_count
+    }
+
+    pub fn get_decode_errors(&self) -> &HashMap<&'static str, u64> {
+        &self.decode_errors
+    }
+
+    pub fn get_decode_errors_for(&self, layer: &str) -> u64 {
+        self.decode_errors.get(layer).copied().unwrap_or(0)
+    }
+
+    pub fn get_total_decode_errors(&self) -> u64 {
+        self.decode_errors.values().sum()
+    }
+
+    pub(crate) fn add_kernel(&mut self, received: u32, dropped: u32, freeze_count: u32) {
+        self.received += received as u64;
+        self.dropped += dropped as u64;
+        self.freeze_count += freeze_count as u64;
+    }
+
+    pub(crate) fn set_interface_dropped(&mut self, interface_dropped: u64) {
+        self.interface_dropped = interface_dropped;
+    }
+
+    pub fn record_decode_error(&mut self, error: &DecodeError) {
+        *self.decode_errors.entry(error.get_layer()).or_insert(0) += 1;
+    }
+
+    pub fn to_interface_statistics_block(&self, interface_id: u32, timestamp: u64) -> InterfaceStatisticsBlock {
+        let mut block = InterfaceStatisticsBlock::new(interface_id, timestamp);
+        block.set_counter(ISB_IF_RECV, self.received);
+        block.set_counter(ISB_IF_DROP, self.interface_dropped);
+        block.set_counter(ISB_OS_DROP, self.dropped);
+        block
+    }
+}
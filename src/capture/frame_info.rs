@@ -0,0 +1,67 @@
+use crate::packet::inter::decode_error::DecodeError;
+use crate::packet::inter::interfaces::Interfaces;
+use crate::packet::inter::packet_types::PacketTypes;
+use crate::packet::packet::Packet;
+
+//WHAT THE KERNEL TELLS US ABOUT A FRAME NEXT TO ITS BYTES
+#[derive(Copy, Clone, Debug)]
+pub struct FrameInfo {
+    frame_time: u128,
+    original_length: usize,
+    packet_type: PacketTypes,
+    if_index: i32,
+    hardware_type: u16,
+    vlan: Option<(u16, u16)>
+}
+
+impl FrameInfo {
+
+    pub fn new(frame_time: u128, original_length: usize, packet_type: PacketTypes, if_index: i32, hardware_type: u16) -> Self {
+        Self {
+            frame_time,
+            original_length,
+            packet_type,
+            if_index,
+            hardware_type,
+            vlan: None
+        }
+    }
+
+    //NANOSECONDS SINCE THE UNIX EPOCH
+    pub fn get_frame_time(&self) -> u128 {
+        self.frame_time
+    }
+
+    pub fn get_original_length(&self) -> usize {
+        self.original_length
+    }
+
+    pub fn get_packet_type(&self) -> PacketTypes {
+        self.packet_type
+    }
+
+    pub fn get_if_index(&self) -> i32 {
+        self.if_index
+    }
+
+    pub fn get_hardware_type(&self) -> u16 {
+        self.hardware_type
+    }
+
+    //TPID AND TCI OF THE TAG THE KERNEL STRIPPED, ALREADY PUT BACK INTO THE FRAME
+    pub fn set_vlan(&mut self, vlan: Option<(u16, u16)>) {
+        self.vlan = vlan;
+    }
+
+    pub fn get_vlan(&self) -> Option<(u16, u16)> {
+        self.vlan
+    }
+
+    pub fn to_packet(&self, interface: Interfaces, data: &[u8]) -> Result<Packet, DecodeError> {
+        let mut packet = Packet::with_original_length(interface, self.frame_time, data, self.original_length)?;
+        packet.set_packet_type(Some(self.packet_type));
+        packet.set_if_index(Some(self.if_index));
+        packet.set_hardware_type(Some(self.hardware_type));
+        Ok(packet)
+    }
+}
@@ -0,0 +1,50 @@
+/*
+How the kernel spreads a fanout group's packets over its sockets.
+Hash keeps every packet of a flow on the same socket, Rollover fills one socket before moving to the next
+and Qm follows the NIC's receive queue.
+*/
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum FanoutModes {
+    Hash,
+    LoadBalance,
+    Cpu,
+    Rollover,
+    Random,
+    Qm
+}
+
+impl FanoutModes {
+
+    pub fn from_code(code: u16) -> Result<Self, String> {
+        for c in [Self::Hash, Self::LoadBalance, Self::Cpu, Self::Rollover, Self::Random, Self::Qm] {
+            if c.get_code() == code {
+                return Ok(c);
+            }
+        }
+
+        Err(format!("Couldn't find for code: {}", code))
+    }
+
+    pub fn get_code(&self) -> u16 {
+        match self {
+            Self::Hash => 0,
+            Self::LoadBalance => 1,
+            Self::Cpu => 2,
+            Self::Rollover => 3,
+            Self::Random => 4,
+            Self::Qm => 5
+        }
+    }
+
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        match self {
+            Self::Hash => "Hash",
+            Self::LoadBalance => "Load Balance",
+            Self::Cpu => "CPU",
+            Self::Rollover => "Rollover",
+            Self::Random => "Random",
+            Self::Qm => "Queue Mapping"
+        }.to_string()
+    }
+}
@@ -0,0 +1,3 @@
+pub mod fanout_modes;
+pub mod timestamp_sources;
+pub mod tx_statuses;
@@ -0,0 +1,33 @@
+/*
+User stamps the packet when we read it, Kernel when the socket received it.
+Hardware uses the NIC clock through SO_TIMESTAMPING, it isn't synced to the system clock
+and frames the NIC didn't stamp fall back to the kernel time.
+*/
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum TimestampSources {
+    User,
+    Kernel,
+    Hardware
+}
+
+impl TimestampSources {
+
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        for c in [Self::User, Self::Kernel, Self::Hardware] {
+            if c.to_string().eq_ignore_ascii_case(name) {
+                return Ok(c);
+            }
+        }
+
+        Err(format!("Couldn't find for name: {}", name))
+    }
+
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        match self {
+            Self::User => "User",
+            Self::Kernel => "Kernel",
+            Self::Hardware => "Hardware"
+        }.to_string()
+    }
+}
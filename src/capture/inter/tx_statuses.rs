@@ -0,0 +1,41 @@
+//tp_status OF A TX RING FRAME, THE KERNEL MOVES IT FROM SendRequest THROUGH Sending BACK TO Available
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum TxStatuses {
+    Available,
+    SendRequest,
+    Sending,
+    WrongFormat
+}
+
+impl TxStatuses {
+
+    //TIMESTAMP BITS CAN BE SET ON TOP, ONLY THE LOW BITS ARE THE STATE
+    pub fn from_code(code: u32) -> Result<Self, String> {
+        for c in [Self::Available, Self::SendRequest, Self::Sending, Self::WrongFormat] {
+            if c.get_code() == code & 0x7 {
+                return Ok(c);
+            }
+        }
+
+        Err(format!("Couldn't find for code: {}", code))
+    }
+
+    pub fn get_code(&self) -> u32 {
+        match self {
+            Self::Available => 0,
+            Self::SendRequest => 1,
+            Self::Sending => 2,
+            Self::WrongFormat => 4
+        }
+    }
+
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        match self {
+            Self::Available => "Available",
+            Self::SendRequest => "Send Request",
+            Self::Sending => "Sending",
+            Self::WrongFormat => "Wrong Format"
+        }.to_string()
+    }
+}
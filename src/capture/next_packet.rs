@@ -0,0 +1,29 @@
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use crate::capture::Capture;
+use crate::packet::packet::Packet;
+
+//RESOLVES TO THE NEXT PACKET, DROPPING IT BEFORE THEN LOSES NOTHING AS NOTHING WAS READ YET
+pub struct NextPacket<'a> {
+    capture: &'a mut Capture
+}
+
+impl<'a> NextPacket<'a> {
+
+    pub fn new(capture: &'a mut Capture) -> Self {
+        Self {
+            capture
+        }
+    }
+}
+
+impl Future for NextPacket<'_> {
+
+    type Output = io::Result<Packet>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.capture.poll_next_packet(cx)
+    }
+}
@@ -0,0 +1,33 @@
+use std::io;
+use crate::capture::Capture;
+use crate::packet::packet::Packet;
+
+//ENDS ONCE A READ TIMES OUT OR WOULD BLOCK, SET A READ TIMEOUT OR NONBLOCKING FOR IT TO EVER RETURN NONE
+pub struct Packets<'a> {
+    capture: &'a mut Capture
+}
+
+impl<'a> Packets<'a> {
+
+    pub fn new(capture: &'a mut Capture) -> Self {
+        Self {
+            capture
+        }
+    }
+}
+
+impl<'a> Iterator for Packets<'a> {
+
+    type Item = io::Result<Packet>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            return match self.capture.next_packet() {
+                Ok(packet) => Some(Ok(packet)),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => None,
+                Err(e) => Some(Err(e))
+            };
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use std::{io, mem, thread};
+use std::collections::HashMap;
+use std::os::fd::RawFd;
+use std::sync::{Mutex, OnceLock};
+use std::task::Waker;
+use crate::capture::sys::syscall::{syscall, syscall6, SYS_CLOSE, SYS_EPOLL_CREATE1, SYS_EPOLL_CTL, SYS_EPOLL_PWAIT};
+
+pub const EPOLL_CTL_ADD: i64 = 1;
+pub const EPOLL_CTL_DEL: i64 = 2;
+pub const EPOLL_CTL_MOD: i64 = 3;
+pub const EPOLLIN: u32 = 0x001;
+pub const EPOLLERR: u32 = 0x008;
+pub const EPOLLONESHOT: u32 = 1 << 30;
+pub const EPOLL_CLOEXEC: i64 = 0o2000000;
+const MAX_EVENTS: usize = 64;
+
+static REACTOR: OnceLock<Reactor> = OnceLock::new();
+static INIT: Mutex<()> = Mutex::new(());
+
+//epoll_event IS PACKED ON x86_64 ONLY, EVERYWHERE ELSE THE DATA IS 8 BYTE ALIGNED
+#[cfg_attr(target_arch = "x86_64", repr(C, packed))]
+#[cfg_attr(not(target_arch = "x86_64"), repr(C))]
+#[derive(Copy, Clone)]
+pub struct EpollEvent {
+    events: u32,
+    data: u64
+}
+
+/*
+One epoll descriptor and one thread for the whole process, started the first time a capture has to wait.
+Descriptors are armed one shot, the thread wakes whoever registered and the next poll arms it again.
+*/
+#[derive(Debug)]
+pub struct Reactor {
+    epoll: RawFd,
+    //AN ENTRY MEANS THE DESCRIPTOR WAS ADDED TO EPOLL, THE WAKER IS TAKEN WHEN IT FIRES
+    wakers: Mutex<HashMap<RawFd, Option<Waker>>>
+}
+
+impl Reactor {
+
+    pub fn get() -> io::Result<&'static Self> {
+        if let Some(reactor) = REACTOR.get() {
+            return Ok(reactor);
+        }
+
+        let _guard = INIT.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(reactor) = REACTOR.get() {
+            return Ok(reactor);
+        }
+
+        let epoll = unsafe {
+            syscall(SYS_EPOLL_CREATE1, EPOLL_CLOEXEC, 0, 0, 0, 0)?
+        } as RawFd;
+
+        //THE THREAD ONLY LOOKS UP WAKERS AFTER AN EVENT, BY THEN REACTOR IS SET AS NOTHING IS REGISTERED BEFORE
+        if let Err(e) = thread::Builder::new().name("capture-reactor".to_string()).spawn(move || Self::run(epoll)) {
+            unsafe {
+                syscall(SYS_CLOSE, epoll as i64, 0, 0, 0, 0).ok();
+            }
+
+            return Err(e);
+        }
+
+        Ok(REACTOR.get_or_init(|| Self {
+            epoll,
+            wakers: Mutex::new(HashMap::new())
+        }))
+    }
+
+    //NONE UNTIL SOME CAPTURE HAS HAD TO WAIT, NOTHING CAN BE REGISTERED BEFORE THAT
+    pub fn get_if_running() -> Option<&'static Self> {
+        REACTOR.get()
+    }
+
+    //WAKES THE WAKER ONCE THE DESCRIPTOR IS READABLE, RIGHT AWAY IF IT ALREADY IS
+    pub fn register(&self, fd: RawFd, waker: &Waker) -> io::Result<()> {
+        let mut wakers = self.wakers.lock().unwrap_or_else(|e| e.into_inner());
+
+        let action = match wakers.contains_key(&fd) {
+            true => EPOLL_CTL_MOD,
+            false => EPOLL_CTL_ADD
+        };
+
+        let event = EpollEvent {
+            events: EPOLLIN | EPOLLERR | EPOLLONESHOT,
+            data: fd as u64
+        };
+
+        unsafe {
+            syscall(SYS_EPOLL_CTL, self.epoll as i64, action, fd as i64, &event as *const _ as i64, 0)?
+        };
+
+        wakers.insert(fd, Some(waker.clone()));
+        Ok(())
+    }
+
+    //CALLED BEFORE THE DESCRIPTOR IS CLOSED SO A REUSED NUMBER DOESN'T INHERIT THE OLD REGISTRATION
+    pub fn deregister(&self, fd: RawFd) {
+        let mut wakers = self.wakers.lock().unwrap_or_else(|e| e.into_inner());
+
+        if wakers.remove(&fd).is_some() {
+            unsafe {
+                syscall(SYS_EPOLL_CTL, self.epoll as i64, EPOLL_CTL_DEL, fd as i64, 0, 0).ok();
+            }
+        }
+    }
+
+    //WHETHER THE DESCRIPTOR IS IN THE EPOLL SET, ARMED OR NOT
+    pub fn is_registered(&self, fd: RawFd) -> bool {
+        self.wakers.lock().unwrap_or_else(|e| e.into_inner()).contains_key(&fd)
+    }
+
+    fn run(epoll: RawFd) {
+        let mut events = [EpollEvent { events: 0, data: 0 }; MAX_EVENTS];
+
+        loop {
+            let count = match unsafe {
+                syscall6(SYS_EPOLL_PWAIT, epoll as i64, events.as_mut_ptr() as i64, MAX_EVENTS as i64, -1, 0, mem::size_of::<u64>() as i64)
+            } {
+                Ok(count) => count as usize,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(_) => return
+            };
+
+            let reactor = match REACTOR.get() {
+                Some(reactor) => reactor,
+                None => continue
+            };
+
+            for event in &events[..count] {
+                let fd = event.data as RawFd;
+
+                //TAKEN UNDER THE LOCK BUT WOKEN OUTSIDE IT, THE WAKER MAY POLL AND REGISTER AGAIN STRAIGHT AWAY
+                let waker = reactor.wakers.lock().unwrap_or_else(|e| e.into_inner()).get_mut(&fd).and_then(Option::take);
+
+                if let Some(waker) = waker {
+                    waker.wake();
+                }
+            }
+        }
+    }
+}
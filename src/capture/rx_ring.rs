@@ -0,0 +1,385 @@
+use std::{io, mem, ptr, slice};
+use std::os::fd::RawFd;
+use std::sync::atomic::{fence, Ordering};
+use crate::capture::frame_info::FrameInfo;
+use crate::capture::{Capture, ETH_P_8021Q, PollFd, POLLERR, POLLIN, SOL_PACKET};
+use crate::capture::sys::syscall::{poll, syscall, syscall6, SYS_CLOSE, SYS_MMAP, SYS_MUNMAP, SYS_SET_SOCK_OPT};
+use crate::packet::inter::decode_error::DecodeError;
+use crate::packet::inter::interfaces::Interfaces;
+use crate::packet::inter::packet_types::PacketTypes;
+use crate::packet::packet::Packet;
+
+pub const PACKET_RX_RING: i64 = 5;
+pub const PACKET_VERSION: i64 = 10;
+pub const TPACKET_V3: i32 = 2;
+pub const PROT_READ: i64 = 0x1;
+pub const PROT_WRITE: i64 = 0x2;
+pub const MAP_SHARED: i64 = 0x01;
+pub const TP_STATUS_KERNEL: u32 = 0;
+pub const TP_STATUS_USER: u32 = 1 << 0;
+pub const TP_STATUS_VLAN_VALID: u32 = 1 << 4;
+pub const TP_STATUS_VLAN_TPID_VALID: u32 = 1 << 6;
+pub const TP_STATUS_TS_RAW_HARDWARE: u32 = 1 << 31;
+pub const DEFAULT_FRAME_SIZE: u32 = 2048;
+pub const DEFAULT_RETIRE_TIMEOUT: u32 = 60;
+pub const IMMEDIATE_RETIRE_TIMEOUT: u32 = 1;
+
+/*
+tpacket_block_desc, the tpacket_hdr_v1 union member starts at 8
+tpacket3_hdr fields are read by offset as they sit at the start of each frame, sockaddr_ll follows it
+*/
+const BLOCK_STATUS: usize = 8;
+const BLOCK_NUM_PACKETS: usize = 12;
+const BLOCK_OFFSET_TO_FIRST_PACKET: usize = 16;
+const BLOCK_LENGTH: usize = 20;
+const BLOCK_SEQUENCE_NUMBER: usize = 24;
+const FRAME_HEADER_SIZE: usize = 48;
+const SOCKADDR_LL_SIZE: usize = 20;
+
+#[repr(C)]
+pub struct TPacketReq3 {
+    tp_block_size: u32,
+    tp_block_nr: u32,
+    tp_frame_size: u32,
+    tp_frame_nr: u32,
+    tp_retire_blk_tov: u32,
+    tp_sizeof_priv: u32,
+    tp_feature_req_word: u32
+}
+
+#[derive(Debug)]
+pub struct RxRing {
+    fd: RawFd,
+    interface: Interfaces,
+    map: *mut u8,
+    block_size: usize,
+    block_count: usize,
+    current: usize
+}
+
+impl RxRing {
+
+    //IMMEDIATE MODE HANDS BLOCKS BACK AFTER A MILLISECOND, OTHERWISE THEY FILL UP FOR LONGER
+    pub fn new(capture: &Capture, block_size: u32, block_count: u32) -> io::Result<Self> {
+        let retire_timeout = match capture.is_immediate_mode() {
+            true => IMMEDIATE_RETIRE_TIMEOUT,
+            false => DEFAULT_RETIRE_TIMEOUT
+        };
+
+        Self::with_timeout(capture, block_size, block_count, retire_timeout)
+    }
+
+    //RETIRE TIMEOUT IS IN MILLISECONDS, A BLOCK IS HANDED BACK PARTIALLY FILLED ONCE IT EXPIRES
+    pub fn with_timeout(capture: &Capture, block_size: u32, block_count: u32, retire_timeout: u32) -> io::Result<Self> {
+        if block_size == 0 || !block_size.is_multiple_of(4096) || block_count == 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Block size must be a non zero multiple of the page size"));
+        }
+
+        let fd = capture.get_fd();
+
+        let version = TPACKET_V3;
+        unsafe {
+            syscall(SYS_SET_SOCK_OPT, fd as i64, SOL_PACKET, PACKET_VERSION, &version as *const _ as i64, mem::size_of::<i32>() as i64)?
+        };
+
+        let frame_size = DEFAULT_FRAME_SIZE.min(block_size);
+        let req = TPacketReq3 {
+            tp_block_size: block_size,
+            tp_block_nr: block_count,
+            tp_frame_size: frame_size,
+            tp_frame_nr: (block_size / frame_size) * block_count,
+            tp_retire_blk_tov: retire_timeout,
+            tp_sizeof_priv: 0,
+            tp_feature_req_word: 0
+        };
+
+        unsafe {
+            syscall(SYS_SET_SOCK_OPT, fd as i64, SOL_PACKET, PACKET_RX_RING, &req as *const _ as i64, mem::size_of::<TPacketReq3>() as i64)?
+        };
+
+        let size = block_size as usize * block_count as usize;
+        let map = unsafe {
+            syscall6(SYS_MMAP, 0, size as i64, PROT_READ | PROT_WRITE, MAP_SHARED, fd as i64, 0)?
+        };
+
+        //OUR OWN DESCRIPTOR SO POLLING KEEPS WORKING IF THE CAPTURE IS DROPPED FIRST
+        let fd = match capture.dup_fd() {
+            Ok(fd) => fd,
+            Err(e) => {
+                unsafe {
+                    syscall(SYS_MUNMAP, map, size as i64, 0, 0, 0).ok();
+                }
+
+                return Err(e);
+            }
+        };
+
+        Ok(Self {
+            fd,
+            interface: capture.get_device().get_interface(),
+            map: map as *mut u8,
+            block_size: block_size as usize,
+            block_count: block_count as usize,
+            current: 0
+        })
+    }
+
+    pub fn get_block_size(&self) -> usize {
+        self.block_size
+    }
+
+    pub fn get_block_count(&self) -> usize {
+        self.block_count
+    }
+
+    pub fn get_interface(&self) -> Interfaces {
+        self.interface
+    }
+
+    //TIMEOUT IS IN MILLISECONDS, -1 WAITS FOREVER. RETURNS NONE IF NOTHING ARRIVED IN TIME
+    pub fn next_block(&mut self, timeout: i32) -> io::Result<Option<RingBlock<'_>>> {
+        if !self.is_block_ready() {
+            let mut pollfd = PollFd::new(self.fd, POLLIN | POLLERR);
+
+            poll(slice::from_mut(&mut pollfd), timeout)?;
+
+            if !self.is_block_ready() {
+                return Ok(None);
+            }
+        }
+
+        fence(Ordering::Acquire);
+
+        let block = unsafe {
+            slice::from_raw_parts(self.map.add(self.current * self.block_size), self.block_size)
+        };
+
+        Ok(Some(RingBlock {
+            ring: self,
+            block
+        }))
+    }
+
+    fn status(&self) -> *mut u32 {
+        unsafe {
+            self.map.add(self.current * self.block_size + BLOCK_STATUS) as *mut u32
+        }
+    }
+
+    fn is_block_ready(&self) -> bool {
+        unsafe {
+            ptr::read_volatile(self.status()) & TP_STATUS_USER != 0
+        }
+    }
+
+    fn release_block(&mut self) {
+        fence(Ordering::Release);
+
+        unsafe {
+            ptr::write_volatile(self.status(), TP_STATUS_KERNEL);
+        }
+
+        self.current = (self.current + 1) % self.block_count;
+    }
+}
+
+//THE MAPPING IS OWNED BY THE RING SO IT CAN MOVE BETWEEN THREADS
+unsafe impl Send for RxRing {}
+
+impl Drop for RxRing {
+
+    fn drop(&mut self) {
+        unsafe {
+            syscall(SYS_MUNMAP, self.map as i64, (self.block_size * self.block_count) as i64, 0, 0, 0).ok();
+            syscall(SYS_CLOSE, self.fd as i64, 0, 0, 0, 0).ok();
+        }
+    }
+}
+
+//HANDED BACK TO THE KERNEL WHEN DROPPED
+pub struct RingBlock<'a> {
+    ring: &'a mut RxRing,
+    block: &'a [u8]
+}
+
+impl<'a> RingBlock<'a> {
+
+    pub fn get_sequence_number(&self) -> u64 {
+        u64::from_ne_bytes(self.block[BLOCK_SEQUENCE_NUMBER..BLOCK_SEQUENCE_NUMBER + 8].try_into().unwrap())
+    }
+
+    pub fn get_num_packets(&self) -> u32 {
+        read_u32(self.block, BLOCK_NUM_PACKETS)
+    }
+
+    pub fn get_length(&self) -> u32 {
+        read_u32(self.block, BLOCK_LENGTH)
+    }
+
+    pub fn frames(&self) -> RingFrames<'_> {
+        RingFrames {
+            block: self.block,
+            interface: self.ring.interface,
+            offset: read_u32(self.block, BLOCK_OFFSET_TO_FIRST_PACKET) as usize,
+            remaining: self.get_num_packets()
+        }
+    }
+}
+
+impl Drop for RingBlock<'_> {
+
+    fn drop(&mut self) {
+        self.ring.release_block();
+    }
+}
+
+pub struct RingFrames<'a> {
+    block: &'a [u8],
+    interface: Interfaces,
+    offset: usize,
+    remaining: u32
+}
+
+impl<'a> Iterator for RingFrames<'a> {
+
+    type Item = RingFrame<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 || self.offset + FRAME_HEADER_SIZE + SOCKADDR_LL_SIZE > self.block.len() {
+            return None;
+        }
+
+        let header = &self.block[self.offset..];
+        let mac = u16::from_ne_bytes([header[24], header[25]]) as usize;
+        let snap_length = read_u32(header, 12) as usize;
+        let data = &header[mac.min(header.len())..(mac + snap_length).min(header.len())];
+
+        let frame = RingFrame {
+            interface: self.interface,
+            header: &header[..FRAME_HEADER_SIZE + SOCKADDR_LL_SIZE],
+            data
+        };
+
+        self.remaining -= 1;
+        self.offset = match read_u32(header, 0) {
+            0 => self.block.len(),
+            next => self.offset + next as usize
+        };
+
+        Some(frame)
+    }
+}
+
+//POINTS STRAIGHT INTO THE RING, NOTHING IS COPIED UNTIL IT'S DECODED
+#[derive(Debug, Clone, Copy)]
+pub struct RingFrame<'a> {
+    interface: Interfaces,
+    header: &'a [u8],
+    data: &'a [u8]
+}
+
+impl<'a> RingFrame<'a> {
+
+    pub fn get_seconds(&self) -> u32 {
+        read_u32(self.header, 4)
+    }
+
+    pub fn get_nanoseconds(&self) -> u32 {
+        read_u32(self.header, 8)
+    }
+
+    pub fn get_snap_length(&self) -> u32 {
+        read_u32(self.header, 12)
+    }
+
+    pub fn get_length(&self) -> u32 {
+        read_u32(self.header, 16)
+    }
+
+    pub fn get_status(&self) -> u32 {
+        read_u32(self.header, 20)
+    }
+
+    pub fn get_rx_hash(&self) -> u32 {
+        read_u32(self.header, 28)
+    }
+
+    pub fn get_vlan_tci(&self) -> u32 {
+        read_u32(self.header, 32)
+    }
+
+    pub fn get_vlan_tpid(&self) -> u16 {
+        u16::from_ne_bytes([self.header[36], self.header[37]])
+    }
+
+    pub fn get_packet_type(&self) -> PacketTypes {
+        PacketTypes::from_code(self.header[FRAME_HEADER_SIZE + 10])
+    }
+
+    pub fn get_if_index(&self) -> i32 {
+        i32::from_ne_bytes([self.header[FRAME_HEADER_SIZE + 4], self.header[FRAME_HEADER_SIZE + 5], self.header[FRAME_HEADER_SIZE + 6], self.header[FRAME_HEADER_SIZE + 7]])
+    }
+
+    pub fn get_hardware_type(&self) -> u16 {
+        u16::from_ne_bytes([self.header[FRAME_HEADER_SIZE + 8], self.header[FRAME_HEADER_SIZE + 9]])
+    }
+
+    //THE STRIPPED TAG AS TPID AND TCI, OLDER KERNELS DON'T REPORT THE TPID SO 802.1Q IS ASSUMED
+    pub fn get_vlan(&self) -> Option<(u16, u16)> {
+        let status = self.get_status();
+        if status & TP_STATUS_VLAN_VALID == 0 {
+            return None;
+        }
+
+        let tpid = match status & TP_STATUS_VLAN_TPID_VALID {
+            0 => ETH_P_8021Q,
+            _ => self.get_vlan_tpid()
+        };
+
+        Some((tpid, self.get_vlan_tci() as u16))
+    }
+
+    //THE LENGTH COUNTS THE TAG WHEN IT GETS PUT BACK
+    pub fn get_info(&self) -> FrameInfo {
+        let vlan = self.get_vlan();
+        let length = self.get_length() as usize + if vlan.is_some() { 4 } else { 0 };
+
+        let mut info = FrameInfo::new(self.get_frame_time(), length, self.get_packet_type(), self.get_if_index(), self.get_hardware_type());
+        info.set_vlan(vlan);
+        info
+    }
+
+    //KERNEL TIMESTAMP IN NANOSECONDS, MATCHES PACKET FRAME TIME
+    pub fn get_frame_time(&self) -> u128 {
+        self.get_seconds() as u128 * 1_000_000_000 + self.get_nanoseconds() as u128
+    }
+
+    //SET WHEN THE NIC STAMPED THE FRAME RATHER THAN THE KERNEL
+    pub fn is_hardware_timestamp(&self) -> bool {
+        self.get_status() & TP_STATUS_TS_RAW_HARDWARE != 0
+    }
+
+    pub fn get_data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    pub fn to_packet(&self) -> Result<Packet, DecodeError> {
+        let info = self.get_info();
+
+        match info.get_vlan() {
+            Some((tpid, tci)) if self.data.len() >= 12 => {
+                let mut data = Vec::with_capacity(self.data.len() + 4);
+                data.extend_from_slice(&self.data[..12]);
+                data.extend_from_slice(&tpid.to_be_bytes());
+                data.extend_from_slice(&tci.to_be_bytes());
+                data.extend_from_slice(&self.data[12..]);
+                info.to_packet(self.interface, &data)
+            }
+            _ => info.to_packet(self.interface, self.data)
+        }
+    }
+}
+
+fn read_u32(buf: &[u8], offset: usize) -> u32 {
+    u32::from_ne_bytes([buf[offset], buf[offset + 1], buf[offset + 2], buf[offset + 3]])
+}
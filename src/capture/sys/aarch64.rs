@@ -0,0 +1,24 @@
+pub const SYS_EPOLL_CREATE1: i64 = 20;
+pub const SYS_EPOLL_CTL: i64 = 21;
+pub const SYS_EPOLL_PWAIT: i64 = 22;
+pub const SYS_FCNTL: i64 = 25;
+pub const SYS_IOCTL: i64 = 29;
+pub const SYS_CLOSE: i64 = 57;
+pub const SYS_PPOLL: i64 = 73;
+pub const SYS_SOCKET: i64 = 198;
+pub const SYS_SOCKETPAIR: i64 = 199;
+pub const SYS_BIND: i64 = 200;
+pub const SYS_SENDTO: i64 = 206;
+pub const SYS_RECV_FROM: i64 = 207;
+pub const SYS_SET_SOCK_OPT: i64 = 208;
+pub const SYS_GET_SOCK_OPT: i64 = 209;
+pub const SYS_RECV_MSG: i64 = 212;
+pub const SYS_MUNMAP: i64 = 215;
+pub const SYS_MMAP: i64 = 222;
+
+//NUMBER IN x8, ARGUMENTS IN x0 TO x5, THE RESULT COMES BACK IN x0
+pub(crate) unsafe fn raw_syscall6(number: i64, a1: i64, a2: i64, a3: i64, a4: i64, a5: i64, a6: i64) -> i64 {
+    let ret: i64;
+    core::arch::asm!("svc 0", in("x8") number, inlateout("x0") a1 => ret, in("x1") a2, in("x2") a3, in("x3") a4, in("x4") a5, in("x5") a6, options(nostack));
+    ret
+}
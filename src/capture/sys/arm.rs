@@ -0,0 +1,43 @@
+pub const SYS_CLOSE: i64 = 6;
+pub const SYS_IOCTL: i64 = 54;
+pub const SYS_FCNTL: i64 = 55;
+pub const SYS_MUNMAP: i64 = 91;
+//mmap2, THE OFFSET IS IN PAGES BUT WE ALWAYS MAP FROM 0
+pub const SYS_MMAP: i64 = 192;
+pub const SYS_EPOLL_CTL: i64 = 251;
+pub const SYS_SOCKET: i64 = 281;
+pub const SYS_SOCKETPAIR: i64 = 288;
+pub const SYS_BIND: i64 = 282;
+pub const SYS_SENDTO: i64 = 290;
+pub const SYS_RECV_FROM: i64 = 292;
+pub const SYS_SET_SOCK_OPT: i64 = 294;
+pub const SYS_GET_SOCK_OPT: i64 = 295;
+pub const SYS_RECV_MSG: i64 = 297;
+pub const SYS_PPOLL: i64 = 336;
+pub const SYS_EPOLL_PWAIT: i64 = 346;
+pub const SYS_EPOLL_CREATE1: i64 = 357;
+
+/*
+EABI, number in r7 and arguments in r0 to r5.
+r7 is the frame pointer in thumb code so it can't be an operand, it's swapped in and out around the svc.
+Registers are 32 bit, the result is sign extended so errno stays negative.
+*/
+pub(crate) unsafe fn raw_syscall6(number: i64, a1: i64, a2: i64, a3: i64, a4: i64, a5: i64, a6: i64) -> i64 {
+    let ret: u32;
+    core::arch::asm!(
+        "mov {saved}, r7",
+        "mov r7, {number}",
+        "svc 0",
+        "mov r7, {saved}",
+        number = in(reg) number as u32,
+        saved = out(reg) _,
+        inlateout("r0") a1 as u32 => ret,
+        in("r1") a2 as u32,
+        in("r2") a3 as u32,
+        in("r3") a4 as u32,
+        in("r4") a5 as u32,
+        in("r5") a6 as u32,
+        options(nostack)
+    );
+    ret as i32 as i64
+}
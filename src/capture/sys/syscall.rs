@@ -0,0 +1,57 @@
+use std::io;
+use crate::capture::PollFd;
+
+#[cfg(target_arch = "x86_64")]
+pub use crate::capture::sys::x86_64::*;
+#[cfg(target_arch = "aarch64")]
+pub use crate::capture::sys::aarch64::*;
+#[cfg(target_arch = "riscv64")]
+pub use crate::capture::sys::riscv64::*;
+#[cfg(target_arch = "arm")]
+pub use crate::capture::sys::arm::*;
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64", target_arch = "riscv64", target_arch = "arm")))]
+compile_error!("Capture only has syscall tables for x86_64, aarch64, riscv64 and arm");
+
+//THE KERNEL RETURNS -errno IN THE LAST PAGE OF THE RANGE, ANYTHING ELSE IS A RESULT EVEN IF IT LOOKS NEGATIVE (mmap ON 32 BIT)
+const MAX_ERRNO: i64 = 4095;
+
+//timespec WITH THE KERNEL'S long, 32 BIT ON arm
+#[repr(C)]
+pub struct TimeSpec {
+    tv_sec: isize,
+    tv_nsec: isize
+}
+
+pub(crate) unsafe fn syscall(number: i64, a1: i64, a2: i64, a3: i64, a4: i64, a5: i64) -> io::Result<i64> {
+    syscall6(number, a1, a2, a3, a4, a5, 0)
+}
+
+pub(crate) unsafe fn syscall6(number: i64, a1: i64, a2: i64, a3: i64, a4: i64, a5: i64, a6: i64) -> io::Result<i64> {
+    let res = raw_syscall6(number, a1, a2, a3, a4, a5, a6);
+
+    if (-MAX_ERRNO..0).contains(&res) {
+        return Err(io::Error::from_raw_os_error(-res as i32));
+    }
+
+    Ok(res)
+}
+
+//TIMEOUT IS IN MILLISECONDS, -1 WAITS FOREVER. ppoll AS NEWER ARCHITECTURES DON'T HAVE poll
+pub fn poll(fds: &mut [PollFd], timeout: i32) -> io::Result<usize> {
+    let timespec = TimeSpec {
+        tv_sec: (timeout / 1000) as isize,
+        tv_nsec: ((timeout % 1000) * 1_000_000) as isize
+    };
+
+    let timespec_ptr = match timeout < 0 {
+        true => 0,
+        false => &timespec as *const _ as i64
+    };
+
+    let res = unsafe {
+        syscall(SYS_PPOLL, fds.as_mut_ptr() as i64, fds.len() as i64, timespec_ptr, 0, 0)?
+    };
+
+    Ok(res as usize)
+}
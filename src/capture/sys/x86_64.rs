@@ -0,0 +1,24 @@
+pub const SYS_CLOSE: i64 = 3;
+pub const SYS_MMAP: i64 = 9;
+pub const SYS_MUNMAP: i64 = 11;
+pub const SYS_IOCTL: i64 = 16;
+pub const SYS_SOCKET: i64 = 41;
+pub const SYS_SOCKETPAIR: i64 = 53;
+pub const SYS_SENDTO: i64 = 44;
+pub const SYS_RECV_FROM: i64 = 45;
+pub const SYS_RECV_MSG: i64 = 47;
+pub const SYS_BIND: i64 = 49;
+pub const SYS_SET_SOCK_OPT: i64 = 54;
+pub const SYS_GET_SOCK_OPT: i64 = 55;
+pub const SYS_FCNTL: i64 = 72;
+pub const SYS_EPOLL_CTL: i64 = 233;
+pub const SYS_PPOLL: i64 = 271;
+pub const SYS_EPOLL_PWAIT: i64 = 281;
+pub const SYS_EPOLL_CREATE1: i64 = 291;
+
+//NUMBER IN rax, ARGUMENTS IN rdi rsi rdx r10 r8 r9, THE KERNEL CLOBBERS rcx AND r11
+pub(crate) unsafe fn raw_syscall6(number: i64, a1: i64, a2: i64, a3: i64, a4: i64, a5: i64, a6: i64) -> i64 {
+    let ret: i64;
+    core::arch::asm!("syscall", inlateout("rax") number => ret, in("rdi") a1, in("rsi") a2, in("rdx") a3, in("r10") a4, in("r8") a5, in("r9") a6, lateout("rcx") _, lateout("r11") _, options(nostack));
+    ret
+}
@@ -0,0 +1,252 @@
+use std::{io, mem, ptr};
+use std::sync::atomic::{fence, Ordering};
+use crate::capture::batch_result::BatchResult;
+use crate::capture::inter::tx_statuses::TxStatuses;
+use crate::capture::rx_ring::{MAP_SHARED, PACKET_VERSION, PROT_READ, PROT_WRITE};
+use crate::capture::sys::syscall::{syscall, syscall6, SYS_MMAP, SYS_MUNMAP, SYS_SENDTO, SYS_SET_SOCK_OPT};
+use crate::capture::{Capture, SockAddrLl, SOL_PACKET};
+use crate::devices::Device;
+use crate::packet::packet::Packet;
+
+pub const PACKET_TX_RING: i64 = 13;
+pub const PACKET_LOSS: i64 = 14;
+pub const TPACKET_V2: i32 = 1;
+pub const DEFAULT_TX_FRAME_SIZE: u32 = 2048;
+const PAGE_SIZE: u32 = 4096;
+
+//tpacket2_hdr ALIGNED TO 16, THE KERNEL READS THE FRAME RIGHT AFTER IT
+const FRAME_HEADER_SIZE: usize = 32;
+const FRAME_STATUS: usize = 0;
+const FRAME_LENGTH: usize = 4;
+
+#[repr(C)]
+pub struct TPacketReq {
+    tp_block_size: u32,
+    tp_block_nr: u32,
+    tp_frame_size: u32,
+    tp_frame_nr: u32
+}
+
+/*
+Frames are copied into the mapped ring and marked for sending, one sendto hands every marked frame to the driver.
+The ring has its own send only socket so it can sit next to an RX ring on the same device without
+a second copy of every received frame being queued on it.
+*/
+pub struct TxRing {
+    capture: Capture,
+    destination: SockAddrLl,
+    map: *mut u8,
+    block_size: usize,
+    block_count: usize,
+    frame_size: usize,
+    frames_per_block: usize,
+    current: usize,
+    queued: usize
+}
+
+impl TxRing {
+
+    pub fn new(device: &Device, frame_count: u32) -> io::Result<Self> {
+        Self::with_frame_size(device, DEFAULT_TX_FRAME_SIZE, frame_count)
+    }
+
+    //FRAME SIZE COUNTS THE 32 BYTE HEADER AND MUST BE A MULTIPLE OF 16
+    pub fn with_frame_size(device: &Device, frame_size: u32, frame_count: u32) -> io::Result<Self> {
+        if frame_size as usize <= FRAME_HEADER_SIZE || !frame_size.is_multiple_of(16) || frame_count == 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Frame size must be a multiple of 16 larger than the frame header"));
+        }
+
+        let capture = Capture::with_protocol(device, 0)?;
+        let fd = capture.get_fd();
+
+        let version = TPACKET_V2;
+        unsafe {
+            syscall(SYS_SET_SOCK_OPT, fd as i64, SOL_PACKET, PACKET_VERSION, &version as *const _ as i64, mem::size_of::<i32>() as i64)?
+        };
+
+        //WITHOUT IT THE KERNEL STOPS AT A FRAME IT REFUSES AND NEVER MOVES PAST IT, WITH IT THE FRAME IS DROPPED AND HANDED BACK
+        let loss = 1i32;
+        unsafe {
+            syscall(SYS_SET_SOCK_OPT, fd as i64, SOL_PACKET, PACKET_LOSS, &loss as *const _ as i64, mem::size_of::<i32>() as i64)?
+        };
+
+        let block_size = frame_size.div_ceil(PAGE_SIZE) * PAGE_SIZE;
+        let frames_per_block = block_size / frame_size;
+        let block_count = frame_count.div_ceil(frames_per_block);
+
+        let req = TPacketReq {
+            tp_block_size: block_size,
+            tp_block_nr: block_count,
+            tp_frame_size: frame_size,
+            tp_frame_nr: block_count * frames_per_block
+        };
+
+        unsafe {
+            syscall(SYS_SET_SOCK_OPT, fd as i64, SOL_PACKET, PACKET_TX_RING, &req as *const _ as i64, mem::size_of::<TPacketReq>() as i64)?
+        };
+
+        let size = block_size as usize * block_count as usize;
+        let map = unsafe {
+            syscall6(SYS_MMAP, 0, size as i64, PROT_READ | PROT_WRITE, MAP_SHARED, fd as i64, 0)?
+        };
+
+        //PROTOCOL 0 LETS THE KERNEL TAKE EACH FRAME'S ETHERTYPE FROM ITS HEADER
+        let destination = match capture.destination(&[]) {
+            Ok(destination) => SockAddrLl {
+                sll_protocol: 0,
+                ..destination
+            },
+            Err(e) => {
+                unsafe {
+                    syscall(SYS_MUNMAP, map, size as i64, 0, 0, 0).ok();
+                }
+
+                return Err(e);
+            }
+        };
+
+        Ok(Self {
+            capture,
+            destination,
+            map: map as *mut u8,
+            block_size: block_size as usize,
+            block_count: block_count as usize,
+            frame_size: frame_size as usize,
+            frames_per_block: frames_per_block as usize,
+            current: 0,
+            queued: 0
+        })
+    }
+
+    pub fn get_frame_size(&self) -> usize {
+        self.frame_size
+    }
+
+    pub fn get_frame_count(&self) -> usize {
+        self.block_count * self.frames_per_block
+    }
+
+    //LARGEST FRAME THAT FITS AFTER THE HEADER
+    pub fn get_max_length(&self) -> usize {
+        self.frame_size - FRAME_HEADER_SIZE
+    }
+
+    pub fn get_device(&self) -> &Device {
+        self.capture.get_device()
+    }
+
+    pub fn get_status(&self, index: usize) -> TxStatuses {
+        let status = unsafe {
+            ptr::read_volatile(self.frame(index).add(FRAME_STATUS) as *const u32)
+        };
+
+        TxStatuses::from_code(status).unwrap_or(TxStatuses::WrongFormat)
+    }
+
+    /*
+    Copies the frame into the ring, WouldBlock when every frame is still waiting to be sent.
+    The position only moves on once the frame is marked for sending, it stays in step with the kernel's because
+    PACKET_LOSS makes the kernel step over frames it refuses instead of stopping on them.
+    */
+    pub fn queue(&mut self, data: &[u8]) -> io::Result<usize> {
+        if data.len() > self.get_max_length() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Frame doesn't fit in a ring frame"));
+        }
+
+        let index = self.current;
+
+        match self.get_status(index) {
+            TxStatuses::Available | TxStatuses::WrongFormat => {}
+            _ => return Err(io::Error::from(io::ErrorKind::WouldBlock))
+        }
+
+        unsafe {
+            let frame = self.frame(index);
+            ptr::copy_nonoverlapping(data.as_ptr(), frame.add(FRAME_HEADER_SIZE), data.len());
+            ptr::write_volatile(frame.add(FRAME_LENGTH) as *mut u32, data.len() as u32);
+
+            fence(Ordering::Release);
+            ptr::write_volatile(frame.add(FRAME_STATUS) as *mut u32, TxStatuses::SendRequest.get_code());
+        }
+
+        self.current = (self.current + 1) % self.get_frame_count();
+        self.queued += 1;
+
+        Ok(index)
+    }
+
+    //BLOCKS UNTIL THE KERNEL HAS GONE THROUGH EVERY QUEUED FRAME, RETURNS THE BYTES SENT
+    //ON ERROR THE FRAMES IT DIDN'T REACH STAY QUEUED FOR THE NEXT FLUSH
+    pub fn flush(&mut self) -> io::Result<usize> {
+        if self.queued == 0 {
+            return Ok(0);
+        }
+
+        let len = unsafe {
+            syscall6(SYS_SENDTO, self.capture.get_fd() as i64, 0, 0, 0, &self.destination as *const _ as i64, mem::size_of::<SockAddrLl>() as i64)?
+        };
+
+        fence(Ordering::Acquire);
+        self.queued = 0;
+
+        Ok(len as usize)
+    }
+
+    /*
+    One status per packet that made it into the ring, in order. Available means the kernel is done with it, it went out
+    or was refused and dropped, SendRequest that a failed flush never reached it.
+    A flush that fails part way still hands back the statuses of the packets before it alongside the error.
+    */
+    pub fn send_batch(&mut self, packets: &[Packet]) -> BatchResult {
+        let mut statuses = Vec::with_capacity(packets.len());
+
+        if let Err(e) = self.flush() {
+            return BatchResult::new(statuses, Some(e));
+        }
+
+        for chunk in packets.chunks(self.get_frame_count()) {
+            let mut indexes = Vec::with_capacity(chunk.len());
+            let mut res = Ok(());
+
+            for packet in chunk {
+                match self.queue(&packet.to_bytes()) {
+                    Ok(index) => indexes.push(index),
+                    Err(e) => {
+                        res = Err(e);
+                        break;
+                    }
+                }
+            }
+
+            //WHAT GOT QUEUED BEFORE A FAILURE STILL GOES OUT
+            let res = res.and(self.flush().map(|_| ()));
+            statuses.extend(indexes.into_iter().map(|index| self.get_status(index)));
+
+            if let Err(e) = res {
+                return BatchResult::new(statuses, Some(e));
+            }
+        }
+
+        BatchResult::new(statuses, None)
+    }
+
+    fn frame(&self, index: usize) -> *mut u8 {
+        let offset = (index / self.frames_per_block) * self.block_size + (index % self.frames_per_block) * self.frame_size;
+
+        unsafe {
+            self.map.add(offset)
+        }
+    }
+}
+
+//THE MAPPING IS OWNED BY THE RING SO IT CAN MOVE BETWEEN THREADS, THE SOCKET CLOSES WITH THE CAPTURE
+unsafe impl Send for TxRing {}
+
+impl Drop for TxRing {
+
+    fn drop(&mut self) {
+        unsafe {
+            syscall(SYS_MUNMAP, self.map as i64, (self.block_size * self.block_count) as i64, 0, 0, 0).ok();
+        }
+    }
+}
@@ -1,5 +1,9 @@
+
+pub mod bpf;
 pub mod devices;
 pub mod packet;
+pub mod pcap;
+pub mod pcapng;
 
 /*
 [
@@ -29,29 +33,106 @@ pub mod packet;
 
 #[cfg(target_os = "linux")]
 pub mod capture {
-    use std::{io, mem};
-    use std::os::fd::RawFd;
+    pub mod inter;
+    pub mod batch_result;
+    pub mod capture_handle;
+    pub mod capture_stats;
+    pub mod frame_info;
+    #[cfg(feature = "async")]
+    pub mod next_packet;
+    pub mod packets;
+    #[cfg(feature = "async")]
+    pub mod reactor;
+    pub mod capture_reader;
+    pub mod capture_sender;
+    pub mod rx_ring;
+    pub mod sys;
+    pub mod tx_ring;
+
+    use std::{fs, io, mem, slice};
+    use std::os::fd::{AsFd, AsRawFd, BorrowedFd, RawFd};
+    use std::sync::mpsc;
+    use std::sync::mpsc::Receiver;
+    #[cfg(feature = "async")]
+    use std::task::{Context, Poll};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    use crate::bpf::bpf_program::{BpfProgram, DEFAULT_SNAP_LENGTH};
+    use crate::bpf::inter::bpf_instruction::BpfInstruction;
+    use crate::capture::capture_handle::CaptureHandle;
+    use crate::capture::capture_reader::CaptureReader;
+    use crate::capture::capture_sender::CaptureSender;
+    use crate::capture::capture_stats::CaptureStats;
+    use crate::capture::frame_info::FrameInfo;
+    use crate::capture::inter::fanout_modes::FanoutModes;
+    use crate::capture::inter::timestamp_sources::TimestampSources;
+    #[cfg(feature = "async")]
+    use crate::capture::next_packet::NextPacket;
+    use crate::capture::packets::Packets;
+    #[cfg(feature = "async")]
+    use crate::capture::reactor::Reactor;
+    use crate::capture::rx_ring::{TP_STATUS_VLAN_TPID_VALID, TP_STATUS_VLAN_VALID};
+    use crate::capture::sys::syscall::*;
     use crate::devices::Device;
-    use crate::packet::packet::{decode_packet, Packet};
+    use crate::packet::inter::packet_types::PacketTypes;
+    use crate::packet::layers::ethernet_frame::inter::ethernet_address::EthernetAddress;
+    use crate::packet::packet::Packet;
 
-    pub const SYS_SOCKET: i64 = 41;
     pub const AF_PACKET: i64 = 17;
     pub const SOCK_RAW: i64 = 3;
     pub const ETH_P_ALL: u16 = 0x0003;
+    pub const ETH_P_8021Q: u16 = 0x8100;
     pub const SOL_SOCKET: i64 = 1;
+    pub const SOL_PACKET: i64 = 263;
     pub const SO_BINDTODEVICE: i64 = 25;
-    pub const SYS_IOCTL: i64 = 16;
-    pub const SYS_BIND: i64 = 49;
-    pub const SYS_SENDTO: i64 = 0x2C;
-    pub const SYS_RECV_FROM: i64 = 45;
-    pub const SYS_SET_SOCK_OPT: i64 = 54;
+    pub const SO_ATTACH_FILTER: i64 = 26;
+    pub const SO_DETACH_FILTER: i64 = 27;
+    pub const SO_RCVBUF: i64 = 8;
+    pub const SO_RCVTIMEO: i64 = 20;
+    pub const MSG_TRUNC: i64 = 0x20;
+    pub const MSG_DONTWAIT: i64 = 0x40;
+    pub const SO_TIMESTAMPNS: i64 = 35;
+    pub const SO_TIMESTAMPING: i64 = 37;
+    pub const SCM_TIMESTAMPNS: i32 = 35;
+    pub const SCM_TIMESTAMPING: i32 = 37;
+    pub const SOF_TIMESTAMPING_RX_HARDWARE: u32 = 1 << 2;
+    pub const SOF_TIMESTAMPING_RX_SOFTWARE: u32 = 1 << 3;
+    pub const SOF_TIMESTAMPING_SOFTWARE: u32 = 1 << 4;
+    pub const SOF_TIMESTAMPING_RAW_HARDWARE: u32 = 1 << 6;
+    pub const PACKET_TIMESTAMP: i64 = 17;
+    pub const SIOCSHWTSTAMP: u64 = 0x89b0;
+    pub const HWTSTAMP_TX_OFF: i32 = 0;
+    pub const HWTSTAMP_FILTER_ALL: i32 = 1;
+    pub const F_GETFL: i64 = 3;
+    pub const F_SETFL: i64 = 4;
+    pub const F_DUPFD_CLOEXEC: i64 = 1030;
+    pub const O_NONBLOCK: i64 = 0o4000;
+    pub const POLLIN: i16 = 0x001;
+    pub const POLLOUT: i16 = 0x004;
+    pub const POLLERR: i16 = 0x008;
+    pub const PACKET_AUXDATA: i64 = 8;
+    pub const PACKET_STATISTICS: i64 = 6;
+    pub const PACKET_FANOUT: i64 = 18;
+    pub const PACKET_FANOUT_FLAG_ROLLOVER: u16 = 0x1000;
+    pub const PACKET_FANOUT_FLAG_DEFRAG: u16 = 0x8000;
+    pub const PACKET_IGNORE_OUTGOING: i64 = 23;
+    pub const PACKET_ADD_MEMBERSHIP: i64 = 1;
+    pub const PACKET_DROP_MEMBERSHIP: i64 = 2;
+    pub const PACKET_MR_MULTICAST: u16 = 0;
+    pub const PACKET_MR_PROMISC: u16 = 1;
+    pub const PACKET_MR_ALLMULTI: u16 = 2;
     pub const IFNAMSIZ: usize = 16;
     pub const SIOCGIFINDEX: u64 = 0x8933;
+    pub const SIOCGIFMTU: u64 = 0x8921;
+    //ETHERNET HEADER WITH ROOM FOR TWO VLAN TAGS, THE MTU ONLY COUNTS THE PAYLOAD
+    pub const LINK_HEADER_LEN: usize = 22;
+    //MOST PACKETS dispatch(0) HANDS OUT IN ONE CALL, UNDER STEADY TRAFFIC THE QUEUE MAY NEVER RUN DRY
+    pub const DISPATCH_BATCH: usize = 256;
 
     #[repr(C)]
     pub struct IfReq {
         ifr_name: [u8; IFNAMSIZ],
         ifr_ifindex: i32,
+        ifr_pad: [u8; 20]
     }
 
     #[repr(C)]
@@ -65,188 +146,1619 @@ pub mod capture {
         sll_addr: [u8; 8],
     }
 
-    #[derive(Debug, Clone)]
+    #[repr(C)]
+    pub struct HwTstampConfig {
+        flags: i32,
+        tx_type: i32,
+        rx_filter: i32
+    }
+
+    #[repr(C)]
+    pub struct IfReqData {
+        ifr_name: [u8; IFNAMSIZ],
+        ifr_data: *mut u8,
+        ifr_pad: [u8; 16]
+    }
+
+    #[repr(C)]
+    pub struct IoVec {
+        iov_base: *mut u8,
+        iov_len: usize
+    }
+
+    #[repr(C)]
+    pub struct MsgHdr {
+        msg_name: *mut u8,
+        msg_namelen: u32,
+        msg_iov: *mut IoVec,
+        msg_iovlen: usize,
+        msg_control: *mut u8,
+        msg_controllen: usize,
+        msg_flags: i32
+    }
+
+    //cmsghdr IS A usize LENGTH, LEVEL AND TYPE, THE DATA FOLLOWS ALIGNED TO usize
+    const CMSG_ALIGN: usize = mem::size_of::<usize>();
+    const CMSG_HEADER_SIZE: usize = CMSG_ALIGN + 8;
+    //TWO OF THE KERNEL'S long, 16 BYTES ON 64 BIT AND 8 ON arm
+    const TIMESPEC_SIZE: usize = 2 * mem::size_of::<isize>();
+
+    #[repr(C)]
+    pub struct TimeVal {
+        tv_sec: isize,
+        tv_usec: isize
+    }
+
+    #[repr(C)]
+    pub struct PollFd {
+        fd: i32,
+        events: i16,
+        revents: i16
+    }
+
+    impl PollFd {
+
+        pub fn new(fd: RawFd, events: i16) -> Self {
+            Self {
+                fd,
+                events,
+                revents: 0
+            }
+        }
+
+        pub fn get_revents(&self) -> i16 {
+            self.revents
+        }
+    }
+
+    #[repr(C)]
+    pub struct SockFprog {
+        len: u16,
+        filter: *const BpfInstruction
+    }
+
+    #[repr(C)]
+    pub struct TPacketStatsV3 {
+        tp_packets: u32,
+        tp_drops: u32,
+        tp_freeze_q_cnt: u32
+    }
+
+    #[repr(C)]
+    pub struct PacketMreq {
+        mr_ifindex: i32,
+        mr_type: u16,
+        mr_alen: u16,
+        mr_address: [u8; 8]
+    }
+
+    //OWNS THE SOCKET, USE try_clone FOR ANOTHER HANDLE ON IT
+    #[derive(Debug)]
     pub struct Capture {
         fd: RawFd,
         device: Device,
-        promiscuous: bool
+        if_index: i32,
+        immediate: bool,
+        ignore_outgoing: bool,
+        fanout: Option<(u16, FanoutModes)>,
+        snap_length: u32,
+        mtu: usize,
+        buffer_size: Option<usize>,
+        buffer: Vec<u8>,
+        timestamp_source: TimestampSources,
+        promiscuous: bool,
+        all_multicast: bool,
+        multicast: Vec<EthernetAddress>,
+        stats: CaptureStats,
+        interface_dropped: u64
     }
 
     impl Capture {
 
         pub fn from_device(device: &Device) -> io::Result<Self> {
+            Self::with_protocol(device, ETH_P_ALL)
+        }
+
+        //PROTOCOL 0 GIVES A SOCKET THAT ONLY SENDS, THE KERNEL NEVER QUEUES RECEIVED FRAMES ON IT
+        pub(crate) fn with_protocol(device: &Device, protocol: u16) -> io::Result<Self> {
             let fd = unsafe {
-                Self::syscall(SYS_SOCKET, AF_PACKET, SOCK_RAW, ETH_P_ALL.to_be() as i64, 0, 0)
+                syscall(SYS_SOCKET, AF_PACKET, SOCK_RAW, protocol.to_be() as i64, 0, 0)?
+            };
+
+            let mut capture = Self::from_fd(device, fd as RawFd);
+
+            capture.set_timestamp_source(TimestampSources::Kernel)?;
+
+            //THE KERNEL STRIPS VLAN TAGS BEFORE WE SEE THEM, AUXDATA HANDS THEM BACK SO THEY CAN BE PUT BACK IN
+            let enable = 1i32;
+            unsafe {
+                syscall(SYS_SET_SOCK_OPT, fd, SOL_PACKET, PACKET_AUXDATA, &enable as *const _ as i64, mem::size_of::<i32>() as i64)?
+            };
+
+            Ok(capture)
+        }
+
+        //TAKES OWNERSHIP OF THE DESCRIPTOR, ANY DATAGRAM SOCKET READS THE SAME WAY SO TESTS CAN USE A SOCKETPAIR
+        pub(crate) fn from_fd(device: &Device, fd: RawFd) -> Self {
+            Self {
+                fd,
+                device: device.clone(),
+                if_index: 0,
+                immediate: false,
+                ignore_outgoing: false,
+                fanout: None,
+                snap_length: DEFAULT_SNAP_LENGTH,
+                mtu: 0,
+                buffer_size: None,
+                buffer: Vec::new(),
+                timestamp_source: TimestampSources::Kernel,
+                promiscuous: false,
+                all_multicast: false,
+                multicast: Vec::new(),
+                stats: CaptureStats::new(),
+                interface_dropped: 0
+            }
+        }
+
+        //ONE OPEN CAPTURE PER WORKER, ALL IN THE SAME GROUP SO THE KERNEL SPLITS THE TRAFFIC BETWEEN THEM
+        pub fn fanout(device: &Device, count: usize, group_id: u16, mode: FanoutModes) -> io::Result<Vec<Self>> {
+            Self::fanout_with_flags(device, count, group_id, mode, 0)
+        }
+
+        pub fn fanout_with_flags(device: &Device, count: usize, group_id: u16, mode: FanoutModes, flags: u16) -> io::Result<Vec<Self>> {
+            if count == 0 {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "Fanout needs at least one capture"));
+            }
+
+            let mut captures = Vec::with_capacity(count);
+
+            for _ in 0..count {
+                let mut capture = Self::from_device(device)?;
+                capture.open()?;
+                capture.set_fanout_with_flags(group_id, mode, flags)?;
+                captures.push(capture);
+            }
+
+            Ok(captures)
+        }
+
+        pub fn get_fd(&self) -> RawFd {
+            self.fd
+        }
+
+        pub fn get_device(&self) -> &Device {
+            &self.device
+        }
+
+        pub fn open(&mut self) -> io::Result<()> {
+            if self.fd < 0 {
+                return Err(io::Error::new(io::ErrorKind::NotConnected, "Capture is closed"));
+            }
+
+            let mut ifreq = IfReq {
+                ifr_name: [0; IFNAMSIZ],
+                ifr_ifindex: 0,
+                ifr_pad: [0; 20]
+            };
+
+            let if_name_bytes = self.device.get_name().into_bytes();
+            if if_name_bytes.len() >= IFNAMSIZ {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "Interface name too long"));
+            }
+
+            ifreq.ifr_name[..if_name_bytes.len()].copy_from_slice(&if_name_bytes);
+
+            unsafe {
+                syscall(SYS_IOCTL, self.fd as i64, SIOCGIFINDEX as i64, &mut ifreq as *mut _ as i64, 0, 0)?
+            };
+
+            let sockaddr = SockAddrLl {
+                sll_family: AF_PACKET as u16,
+                sll_protocol: ETH_P_ALL.to_be(),
+                sll_ifindex: ifreq.ifr_ifindex,
+                sll_hatype: 0,
+                sll_pkttype: 0,
+                sll_halen: 0,
+                sll_addr: [0; 8],
+            };
+
+            unsafe {
+                syscall(SYS_BIND, self.fd as i64, &sockaddr as *const _ as i64, mem::size_of::<SockAddrLl>() as i64, 0, 0)?
+            };
+
+            unsafe {
+                syscall(SYS_SET_SOCK_OPT, self.fd as i64, SOL_SOCKET, SO_BINDTODEVICE, ifreq.ifr_name.as_ptr() as i64, IFNAMSIZ as i64)?
             };
 
-            if fd < 0 {
-                return Err(io::Error::last_os_error());
+            self.if_index = ifreq.ifr_ifindex;
+            self.mtu = self.get_mtu()?;
+            self.interface_dropped = self.read_interface_dropped();
+
+            //MEMBERSHIPS REQUESTED BEFORE OPEN ARE APPLIED NOW THAT WE HAVE AN INTERFACE INDEX
+            if self.promiscuous {
+                self.membership(PACKET_ADD_MEMBERSHIP, PACKET_MR_PROMISC, None)?;
+            }
+
+            if self.all_multicast {
+                self.membership(PACKET_ADD_MEMBERSHIP, PACKET_MR_ALLMULTI, None)?;
+            }
+
+            for address in self.multicast.clone() {
+                self.membership(PACKET_ADD_MEMBERSHIP, PACKET_MR_MULTICAST, Some(address))?;
+            }
+
+            Ok(())
+        }
+
+        //MEMBERSHIPS BELONG TO THE SOCKET, THE KERNEL DROPS THEM ONCE THE LAST DESCRIPTOR ON IT IS CLOSED
+        pub fn close(&mut self) -> io::Result<()> {
+            if self.fd < 0 {
+                return Ok(());
+            }
+
+            #[cfg(feature = "async")]
+            if let Some(reactor) = Reactor::get_if_running() {
+                reactor.deregister(self.fd);
             }
 
+            //THE DESCRIPTOR IS GONE EVEN IF CLOSE REPORTS AN ERROR, IT MUST NOT BE CLOSED TWICE
+            let res = unsafe {
+                syscall(SYS_CLOSE, self.fd as i64, 0, 0, 0, 0)
+            };
+
+            self.fd = -1;
+            self.if_index = 0;
+
+            res.map(|_| ())
+        }
+
+        /*
+        A second descriptor on the same socket, both see the same packets, filter and memberships.
+        Statistics start from zero as the kernel resets its counters for whichever handle reads them.
+        The kernel counts each membership request on the socket, so the clone starts with none of its own. It can add and drop
+        its own without taking away the original's, the interface stays promiscuous while either handle has asked for it.
+        */
+        pub fn try_clone(&self) -> io::Result<Self> {
             Ok(Self {
-                fd: fd as RawFd,
-                device: device.clone(),
-                promiscuous: false
+                fd: self.dup_fd()?,
+                device: self.device.clone(),
+                if_index: self.if_index,
+                immediate: self.immediate,
+                ignore_outgoing: self.ignore_outgoing,
+                fanout: self.fanout,
+                snap_length: self.snap_length,
+                mtu: self.mtu,
+                buffer_size: self.buffer_size,
+                buffer: Vec::new(),
+                timestamp_source: self.timestamp_source,
+                promiscuous: false,
+                all_multicast: false,
+                multicast: Vec::new(),
+                stats: CaptureStats::new(),
+                interface_dropped: self.interface_dropped
             })
         }
 
-        pub fn open(&self) -> io::Result<()> {
+        //THE READER KEEPS THIS HANDLE, THE SENDER GETS A DUPLICATE SO EITHER CAN MOVE TO ANOTHER THREAD
+        pub fn split(self) -> io::Result<(CaptureReader, CaptureSender)> {
+            let sender = CaptureSender::new(self.try_clone()?);
+            Ok((CaptureReader::new(self), sender))
+        }
+
+        pub(crate) fn dup_fd(&self) -> io::Result<RawFd> {
             if self.fd < 0 {
-                return Err(io::Error::last_os_error());
+                return Err(io::Error::new(io::ErrorKind::NotConnected, "Capture is closed"));
             }
 
-            let res = match !self.promiscuous {
-                true => {
-                    let mut ifreq = IfReq {
-                        ifr_name: [0; IFNAMSIZ],
-                        ifr_ifindex: 0,
-                    };
+            let fd = unsafe {
+                syscall(SYS_FCNTL, self.fd as i64, F_DUPFD_CLOEXEC, 0, 0, 0)?
+            };
 
-                    let if_name_bytes = self.device.get_name().into_bytes();
-                    if if_name_bytes.len() >= IFNAMSIZ {
-                        return Err(io::Error::new(io::ErrorKind::InvalidInput, "Interface name too long"));
-                    }
+            Ok(fd as RawFd)
+        }
 
-                    ifreq.ifr_name[..if_name_bytes.len()].copy_from_slice(&if_name_bytes);
+        //RECVFROM ALWAYS DELIVERS ONE PACKET AT A TIME, THIS DECIDES HOW LONG RING BLOCKS WAIT TO FILL
+        pub fn set_immediate_mode(&mut self, immediate: bool) {
+            self.immediate = immediate;
+        }
 
-                    let res = unsafe {
-                        Self::syscall(SYS_IOCTL, self.fd as i64, SIOCGIFINDEX as i64, &mut ifreq as *mut _ as i64, 0, 0)
-                    };
+        pub fn is_immediate_mode(&self) -> bool {
+            self.immediate
+        }
 
-                    if res < 0 {
-                        return Err(io::Error::last_os_error());
-                    }
+        //THE CAPTURE HAS TO BE OPEN, A SOCKET CAN'T LEAVE ITS GROUP ONCE IT JOINED
+        pub fn set_fanout(&mut self, group_id: u16, mode: FanoutModes) -> io::Result<()> {
+            self.set_fanout_with_flags(group_id, mode, 0)
+        }
 
-                    let if_index = ifreq.ifr_ifindex;
+        //FLAGS ARE PACKET_FANOUT_FLAG_*, DEFRAG KEEPS FRAGMENTS OF A FLOW ON THE SAME SOCKET IN HASH MODE
+        pub fn set_fanout_with_flags(&mut self, group_id: u16, mode: FanoutModes, flags: u16) -> io::Result<()> {
+            if self.if_index <= 0 {
+                return Err(io::Error::new(io::ErrorKind::NotConnected, "Capture must be open to join a fanout group"));
+            }
 
-                    let sockaddr = SockAddrLl {
-                        sll_family: AF_PACKET as u16,
-                        sll_protocol: ETH_P_ALL.to_be(),
-                        sll_ifindex: if_index,
-                        sll_hatype: 0,
-                        sll_pkttype: 0,
-                        sll_halen: 0,
-                        sll_addr: [0; 8],
-                    };
+            let value = (group_id as u32) | (((mode.get_code() | flags) as u32) << 16);
 
-                    let res = unsafe {
-                        Self::syscall(SYS_BIND, self.fd as i64, &sockaddr as *const _ as i64, mem::size_of::<SockAddrLl>() as i64, 0, 0)
-                    };
+            unsafe {
+                syscall(SYS_SET_SOCK_OPT, self.fd as i64, SOL_PACKET, PACKET_FANOUT, &value as *const _ as i64, mem::size_of::<u32>() as i64)?
+            };
 
-                    if res < 0 {
-                        return Err(io::Error::last_os_error());
-                    }
+            self.fanout = Some((group_id, mode));
+            Ok(())
+        }
 
-                    unsafe {
-                        Self::syscall(SYS_SET_SOCK_OPT, self.fd as i64, SOL_SOCKET, SO_BINDTODEVICE, ifreq.ifr_name.as_ptr() as i64, IFNAMSIZ as i64)
-                    }
-                }
-                false => {
-                    unsafe {
-                        Self::syscall(SYS_SET_SOCK_OPT, self.fd as i64, SOL_SOCKET, SO_BINDTODEVICE, 0, 0)
-                    }
-                }
+        pub fn get_fanout(&self) -> Option<(u16, FanoutModes)> {
+            self.fanout
+        }
+
+        //DROPS WHAT THIS HOST SENDS BEFORE IT REACHES US, NEEDS LINUX 4.20
+        pub fn set_ignore_outgoing(&mut self, ignore_outgoing: bool) -> io::Result<()> {
+            let value = ignore_outgoing as i32;
+
+            unsafe {
+                syscall(SYS_SET_SOCK_OPT, self.fd as i64, SOL_PACKET, PACKET_IGNORE_OUTGOING, &value as *const _ as i64, mem::size_of::<i32>() as i64)?
             };
 
-            if res < 0 {
-                return Err(io::Error::last_os_error());
+            self.ignore_outgoing = ignore_outgoing;
+            Ok(())
+        }
+
+        pub fn is_ignore_outgoing(&self) -> bool {
+            self.ignore_outgoing
+        }
+
+        //FRAMES ARE CUT TO THE SNAP LENGTH, THE PACKET STILL KNOWS HOW LONG IT WAS ON THE WIRE
+        pub fn set_snap_length(&mut self, snap_length: u32) -> io::Result<()> {
+            if snap_length == 0 {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "Snap length must be non zero"));
             }
 
+            self.snap_length = snap_length;
             Ok(())
         }
 
-        pub fn set_immediate_mode(&self, immediate: bool) {
-            println!("Setting immediate mode for interface {}", self.device.get_name());
+        pub fn get_snap_length(&self) -> u32 {
+            self.snap_length
         }
 
-        pub fn set_promiscuous_mode(&mut self, promiscuous: bool) -> io::Result<()> {
-            if self.fd < 0 {
-                return Err(io::Error::last_os_error());
+        /*
+        Bytes next_packet reads into, None sizes it from the MTU once the capture is open, capped at the snap length.
+        GRO can hand up super packets longer than the MTU, they're cut and marked truncated unless this is raised.
+        */
+        pub fn set_buffer_size(&mut self, buffer_size: Option<usize>) -> io::Result<()> {
+            if buffer_size == Some(0) {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "Buffer size must be non zero"));
             }
 
-            self.promiscuous = promiscuous;
+            self.buffer_size = buffer_size;
             Ok(())
         }
 
-        pub fn send_packet(&self, packet: Packet) -> io::Result<usize> {
-            let packet = packet.to_bytes();
+        pub fn get_buffer_size(&self) -> usize {
+            let snap_length = self.snap_length as usize;
 
-            let len = unsafe {
-                Self::syscall(
-                    SYS_SENDTO,                  // Send syscall number
-                    self.fd as i64,              // File descriptor
-                    packet.as_ptr() as i64,      // Pointer to the data to send
-                    packet.len() as i64,         // Length of the data
-                    0,                            // Flags (0 if no flags needed)
-                    0                             // Address (0 for no address, required for UDP etc.)
-                )
+            match self.buffer_size {
+                Some(buffer_size) => buffer_size.min(snap_length),
+                None if self.mtu == 0 => snap_length,
+                None => (self.mtu + LINK_HEADER_LEN).min(snap_length)
+            }
+        }
+
+        //SO_RCVBUF, THE KERNEL DOUBLES IT FOR BOOKKEEPING AND CAPS IT AT net.core.rmem_max
+        pub fn set_receive_buffer_size(&mut self, size: usize) -> io::Result<()> {
+            let size = size.min(i32::MAX as usize) as i32;
+
+            unsafe {
+                syscall(SYS_SET_SOCK_OPT, self.fd as i64, SOL_SOCKET, SO_RCVBUF, &size as *const _ as i64, mem::size_of::<i32>() as i64)?
+            };
+
+            Ok(())
+        }
+
+        pub fn get_receive_buffer_size(&self) -> io::Result<usize> {
+            let mut size = 0i32;
+            let mut len = mem::size_of::<i32>() as u32;
+
+            unsafe {
+                syscall(SYS_GET_SOCK_OPT, self.fd as i64, SOL_SOCKET, SO_RCVBUF, &mut size as *mut _ as i64, &mut len as *mut _ as i64)?
+            };
+
+            Ok(size as usize)
+        }
+
+        pub fn get_mtu(&self) -> io::Result<usize> {
+            let if_name_bytes = self.device.get_name().into_bytes();
+            if if_name_bytes.len() >= IFNAMSIZ {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "Interface name too long"));
+            }
+
+            //ifr_mtu SITS WHERE ifr_ifindex DOES
+            let mut ifreq = IfReq {
+                ifr_name: [0; IFNAMSIZ],
+                ifr_ifindex: 0,
+                ifr_pad: [0; 20]
+            };
+            ifreq.ifr_name[..if_name_bytes.len()].copy_from_slice(&if_name_bytes);
+
+            unsafe {
+                syscall(SYS_IOCTL, self.fd as i64, SIOCGIFMTU as i64, &mut ifreq as *mut _ as i64, 0, 0)?
+            };
+
+            Ok(ifreq.ifr_ifindex as usize)
+        }
+
+        //HARDWARE NEEDS CAP_NET_ADMIN AND A NIC THAT SUPPORTS IT, THE RING PICKS THE SOURCE UP TOO
+        pub fn set_timestamp_source(&mut self, source: TimestampSources) -> io::Result<()> {
+            let (nanoseconds, timestamping, packet_timestamp) = match source {
+                TimestampSources::User => (0, 0, 0),
+                TimestampSources::Kernel => (1, 0, 0),
+                TimestampSources::Hardware => {
+                    self.enable_hardware_timestamps()?;
+                    (0, SOF_TIMESTAMPING_RX_HARDWARE | SOF_TIMESTAMPING_RAW_HARDWARE | SOF_TIMESTAMPING_RX_SOFTWARE | SOF_TIMESTAMPING_SOFTWARE, SOF_TIMESTAMPING_RAW_HARDWARE)
+                }
             };
 
-            if len > 0 {
-                Ok(len as usize)
-            } else {
-                Err(io::Error::last_os_error())
+            for (level, option, value) in [(SOL_SOCKET, SO_TIMESTAMPNS, nanoseconds), (SOL_SOCKET, SO_TIMESTAMPING, timestamping), (SOL_PACKET, PACKET_TIMESTAMP, packet_timestamp)] {
+                unsafe {
+                    syscall(SYS_SET_SOCK_OPT, self.fd as i64, level, option, &value as *const _ as i64, mem::size_of::<u32>() as i64)?
+                };
             }
+
+            self.timestamp_source = source;
+            Ok(())
         }
 
-        pub fn next_packet(&mut self) -> io::Result<Packet> {
-            let mut buffer = vec![0u8; 4096];
+        pub fn get_timestamp_source(&self) -> TimestampSources {
+            self.timestamp_source
+        }
 
-            let len = unsafe {
-                Self::syscall(
-                    SYS_RECV_FROM,
-                    self.fd as i64,
-                    buffer.as_mut_ptr() as i64,
-                    buffer.len() as i64,
-                    0,
-                    0
-                )
+        //TELLS THE DRIVER TO STAMP EVERY INCOMING FRAME
+        fn enable_hardware_timestamps(&self) -> io::Result<()> {
+            let if_name_bytes = self.device.get_name().into_bytes();
+            if if_name_bytes.len() >= IFNAMSIZ {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "Interface name too long"));
+            }
+
+            let mut config = HwTstampConfig {
+                flags: 0,
+                tx_type: HWTSTAMP_TX_OFF,
+                rx_filter: HWTSTAMP_FILTER_ALL
+            };
+
+            let mut ifreq = IfReqData {
+                ifr_name: [0; IFNAMSIZ],
+                ifr_data: &mut config as *mut _ as *mut u8,
+                ifr_pad: [0; 16]
+            };
+            ifreq.ifr_name[..if_name_bytes.len()].copy_from_slice(&if_name_bytes);
+
+            unsafe {
+                syscall(SYS_IOCTL, self.fd as i64, SIOCSHWTSTAMP as i64, &mut ifreq as *mut _ as i64, 0, 0)?
+            };
+
+            Ok(())
+        }
+
+        //NONE BLOCKS FOREVER, A TIMED OUT READ RETURNS WouldBlock
+        pub fn set_read_timeout(&mut self, timeout: Option<Duration>) -> io::Result<()> {
+            let timeval = match timeout {
+                Some(timeout) if timeout.is_zero() => {
+                    return Err(io::Error::new(io::ErrorKind::InvalidInput, "Zero duration timeout"));
+                }
+                Some(timeout) => TimeVal {
+                    tv_sec: timeout.as_secs().min(isize::MAX as u64) as isize,
+                    tv_usec: timeout.subsec_micros() as isize
+                },
+                None => TimeVal {
+                    tv_sec: 0,
+                    tv_usec: 0
+                }
+            };
+
+            unsafe {
+                syscall(SYS_SET_SOCK_OPT, self.fd as i64, SOL_SOCKET, SO_RCVTIMEO, &timeval as *const _ as i64, mem::size_of::<TimeVal>() as i64)?
+            };
+
+            Ok(())
+        }
+
+        pub fn get_read_timeout(&self) -> io::Result<Option<Duration>> {
+            let mut timeval = TimeVal {
+                tv_sec: 0,
+                tv_usec: 0
+            };
+            let mut len = mem::size_of::<TimeVal>() as u32;
+
+            unsafe {
+                syscall(SYS_GET_SOCK_OPT, self.fd as i64, SOL_SOCKET, SO_RCVTIMEO, &mut timeval as *mut _ as i64, &mut len as *mut _ as i64)?
+            };
+
+            Ok(match (timeval.tv_sec, timeval.tv_usec) {
+                (0, 0) => None,
+                (seconds, micros) => Some(Duration::new(seconds as u64, micros as u32 * 1000))
+            })
+        }
+
+        pub fn set_nonblocking(&mut self, nonblocking: bool) -> io::Result<()> {
+            let flags = unsafe {
+                syscall(SYS_FCNTL, self.fd as i64, F_GETFL, 0, 0, 0)?
+            };
+
+            let flags = match nonblocking {
+                true => flags | O_NONBLOCK,
+                false => flags & !O_NONBLOCK
+            };
+
+            unsafe {
+                syscall(SYS_FCNTL, self.fd as i64, F_SETFL, flags, 0, 0)?
+            };
+
+            Ok(())
+        }
+
+        pub fn is_nonblocking(&self) -> io::Result<bool> {
+            let flags = unsafe {
+                syscall(SYS_FCNTL, self.fd as i64, F_GETFL, 0, 0, 0)?
+            };
+
+            Ok(flags & O_NONBLOCK != 0)
+        }
+
+        //WAITS UNTIL A PACKET CAN BE READ, FALSE IF THE TIMEOUT RAN OUT FIRST
+        pub fn wait(&self, timeout: Option<Duration>) -> io::Result<bool> {
+            let mut pollfd = PollFd::new(self.fd, POLLIN | POLLERR);
+
+            let timeout = match timeout {
+                Some(timeout) => timeout.as_millis().min(i32::MAX as u128) as i32,
+                None => -1
             };
 
-            if len > 0 {
-                Ok(decode_packet(self.device.get_interface(), &buffer[..len as usize]))
+            Ok(poll(slice::from_mut(&mut pollfd), timeout)? > 0)
+        }
+
+        pub fn set_promiscuous_mode(&mut self, promiscuous: bool) -> io::Result<()> {
+            if self.fd < 0 {
+                return Err(io::Error::new(io::ErrorKind::NotConnected, "Capture is closed"));
+            }
 
-            } else {
-                Err(io::Error::last_os_error())
+            if self.promiscuous != promiscuous && self.if_index > 0 {
+                self.membership(Self::membership_action(promiscuous), PACKET_MR_PROMISC, None)?;
             }
+
+            self.promiscuous = promiscuous;
+            Ok(())
         }
 
-        unsafe fn syscall(number: i64, a1: i64, a2: i64, a3: i64, a4: i64, a5: i64) -> i64 {
-            let ret: i64;
-            core::arch::asm!("syscall", in("rax") number, in("rdi") a1, in("rsi") a2, in("rdx") a3, in("r10") a4, in("r8") a5, lateout("rax") ret);
-            ret
+        pub fn is_promiscuous_mode(&self) -> bool {
+            self.promiscuous
         }
-    }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::capture::Capture;
-    use crate::devices::Device;
+        pub fn set_all_multicast(&mut self, all_multicast: bool) -> io::Result<()> {
+            if self.fd < 0 {
+                return Err(io::Error::new(io::ErrorKind::NotConnected, "Capture is closed"));
+            }
 
-    /*
-    update values of dhcp layer
-    try not to use vec::new
-    frame should calculate length on the spot...
+            if self.all_multicast != all_multicast && self.if_index > 0 {
+                self.membership(Self::membership_action(all_multicast), PACKET_MR_ALLMULTI, None)?;
+            }
 
-    - Honestly redo all of the packet class
-    - IE length, interface, and frame time may not be needed...
-    */
+            self.all_multicast = all_multicast;
+            Ok(())
+        }
 
-    #[test]
-    fn test() {
-        let devices = Device::list().unwrap();
-        println!("{:?}", devices);
+        pub fn is_all_multicast(&self) -> bool {
+            self.all_multicast
+        }
 
-        let device = devices.into_iter().find(|d| d.get_name().contains("Ethernet adapter Ethernet:")).unwrap();
-        println!("{:?}", device);
+        pub fn add_multicast(&mut self, address: EthernetAddress) -> io::Result<()> {
+            if self.fd < 0 {
+                return Err(io::Error::new(io::ErrorKind::NotConnected, "Capture is closed"));
+            }
 
-        let mut cap = Capture::from_device(&device).unwrap();
-        cap.open().unwrap();
+            if self.multicast.contains(&address) {
+                return Ok(());
+            }
 
+            if self.if_index > 0 {
+                self.membership(PACKET_ADD_MEMBERSHIP, PACKET_MR_MULTICAST, Some(address))?;
+            }
 
-        while let Ok(packet) = cap.next_packet() {
-            println!("{:x?}", packet);
+            self.multicast.push(address);
+            Ok(())
+        }
+
+        pub fn remove_multicast(&mut self, address: EthernetAddress) -> io::Result<()> {
+            if self.fd < 0 {
+                return Err(io::Error::new(io::ErrorKind::NotConnected, "Capture is closed"));
+            }
+
+            if !self.multicast.contains(&address) {
+                return Ok(());
+            }
+
+            if self.if_index > 0 {
+                self.membership(PACKET_DROP_MEMBERSHIP, PACKET_MR_MULTICAST, Some(address))?;
+            }
+
+            self.multicast.retain(|a| *a != address);
+            Ok(())
+        }
+
+        pub fn get_multicast(&self) -> &Vec<EthernetAddress> {
+            &self.multicast
+        }
+
+        fn membership_action(add: bool) -> i64 {
+            match add {
+                true => PACKET_ADD_MEMBERSHIP,
+                false => PACKET_DROP_MEMBERSHIP
+            }
         }
+
+        fn membership(&self, action: i64, _type: u16, address: Option<EthernetAddress>) -> io::Result<()> {
+            let mut mreq = PacketMreq {
+                mr_ifindex: self.if_index,
+                mr_type: _type,
+                mr_alen: 0,
+                mr_address: [0; 8]
+            };
+
+            if let Some(address) = address {
+                mreq.mr_alen = 6;
+                mreq.mr_address[..6].copy_from_slice(&address.to_bytes());
+            }
+
+            unsafe {
+                syscall(SYS_SET_SOCK_OPT, self.fd as i64, SOL_PACKET, action, &mreq as *const _ as i64, mem::size_of::<PacketMreq>() as i64)?
+            };
+
+            Ok(())
+        }
+
+        //COMPILES A TCPDUMP STYLE EXPRESSION AND HAS THE KERNEL DROP EVERYTHING IT DOESN'T MATCH
+        pub fn set_filter(&mut self, expression: &str) -> io::Result<()> {
+            //THE SNAP LENGTH IS APPLIED WHEN READING, A FILTER THAT CUTS THE FRAME HIDES ITS WIRE LENGTH
+            let program = BpfProgram::compile(expression)?;
+            self.set_program(&program)
+        }
+
+        pub fn set_program(&mut self, program: &BpfProgram) -> io::Result<()> {
+            program.verify()?;
+
+            let fprog = SockFprog {
+                len: program.len() as u16,
+                filter: program.get_instructions().as_ptr()
+            };
+
+            unsafe {
+                syscall(SYS_SET_SOCK_OPT, self.fd as i64, SOL_SOCKET, SO_ATTACH_FILTER, &fprog as *const _ as i64, mem::size_of::<SockFprog>() as i64)?
+            };
+
+            Ok(())
+        }
+
+        pub fn clear_filter(&mut self) -> io::Result<()> {
+            unsafe {
+                syscall(SYS_SET_SOCK_OPT, self.fd as i64, SOL_SOCKET, SO_DETACH_FILTER, 0, 0)?
+            };
+
+            Ok(())
+        }
+
+        pub fn send_packet(&self, packet: Packet) -> io::Result<usize> {
+            self.send_bytes(&packet.to_bytes())
+        }
+
+        //ADDRESSED WITH sockaddr_ll SO IT WORKS WHETHER OR NOT THE CAPTURE WAS OPENED
+        pub fn send_bytes(&self, data: &[u8]) -> io::Result<usize> {
+            let destination = self.destination(data)?;
+
+            let len = unsafe {
+                syscall6(SYS_SENDTO, self.fd as i64, data.as_ptr() as i64, data.len() as i64, 0, &destination as *const _ as i64, mem::size_of::<SockAddrLl>() as i64)?
+            };
+
+            Ok(len as usize)
+        }
+
+        //THE DEVICE'S INDEX AND THE FRAME'S DESTINATION MAC
+        fn destination(&self, frame: &[u8]) -> io::Result<SockAddrLl> {
+            let mut sll_addr = [0; 8];
+            if frame.len() >= 6 {
+                sll_addr[..6].copy_from_slice(&frame[..6]);
+            }
+
+            Ok(SockAddrLl {
+                sll_family: AF_PACKET as u16,
+                sll_protocol: ETH_P_ALL.to_be(),
+                sll_ifindex: self.get_if_index()?,
+                sll_hatype: 0,
+                sll_pkttype: 0,
+                sll_halen: 6,
+                sll_addr
+            })
+        }
+
+        //LOOKED UP BY NAME WHEN THE CAPTURE ISN'T OPEN
+        pub fn get_if_index(&self) -> io::Result<i32> {
+            if self.if_index > 0 {
+                return Ok(self.if_index);
+            }
+
+            let if_name_bytes = self.device.get_name().into_bytes();
+            if if_name_bytes.len() >= IFNAMSIZ {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "Interface name too long"));
+            }
+
+            let mut ifreq = IfReq {
+                ifr_name: [0; IFNAMSIZ],
+                ifr_ifindex: 0,
+                ifr_pad: [0; 20]
+            };
+            ifreq.ifr_name[..if_name_bytes.len()].copy_from_slice(&if_name_bytes);
+
+            unsafe {
+                syscall(SYS_IOCTL, self.fd as i64, SIOCGIFINDEX as i64, &mut ifreq as *mut _ as i64, 0, 0)?
+            };
+
+            Ok(ifreq.ifr_ifindex)
+        }
+
+        pub fn next_packet(&mut self) -> io::Result<Packet> {
+            self.read_packet(0)
+        }
+
+        /*
+        Never blocks, when nothing is queued the waker is registered with the built in epoll reactor.
+        Works whether or not the capture is in nonblocking mode.
+        */
+        #[cfg(feature = "async")]
+        pub fn poll_next_packet(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<Packet>> {
+            loop {
+                match self.read_packet(MSG_DONTWAIT) {
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                        //EPOLL IS LEVEL TRIGGERED SO A PACKET THAT ARRIVED SINCE THE READ WAKES US RIGHT AWAY
+                        let registered = Reactor::get().and_then(|reactor| reactor.register(self.fd, cx.waker()));
+
+                        return match registered {
+                            Ok(()) => Poll::Pending,
+                            Err(e) => Poll::Ready(Err(e))
+                        };
+                    }
+                    res => return Poll::Ready(res)
+                }
+            }
+        }
+
+        #[cfg(feature = "async")]
+        pub fn next_packet_async(&mut self) -> NextPacket<'_> {
+            NextPacket::new(self)
+        }
+
+        //THE BUFFER IS KEPT BETWEEN READS, ONLY THE RECEIVED BYTES ARE COPIED INTO THE PACKET
+        fn read_packet(&mut self, flags: i64) -> io::Result<Packet> {
+            let mut buffer = mem::take(&mut self.buffer);
+            buffer.resize(self.get_buffer_size(), 0);
+
+            let res = match self.recv_frame(&mut buffer, flags) {
+                Ok((info, frame)) => info.to_packet(self.device.get_interface(), frame).map_err(|e| {
+                    self.stats.record_decode_error(&e);
+                    e.into()
+                }),
+                Err(e) => Err(e)
+            };
+
+            self.buffer = buffer;
+            res
+        }
+
+        pub fn packets(&mut self) -> Packets<'_> {
+            Packets::new(self)
+        }
+
+        /*
+        Like pcap_dispatch, hands up to count packets to the callback and returns how many it got.
+        A count of 0 takes whatever is already queued without waiting, at most DISPATCH_BATCH, call wait first to block for traffic.
+        Any other count reads like next_packet so a blocking capture with no timeout waits until it has them all.
+        Frames that don't decode are skipped, they're counted in stats.
+        */
+        pub fn dispatch<F>(&mut self, count: usize, mut callback: F) -> io::Result<usize>
+        where
+            F: FnMut(Packet)
+        {
+            let mut delivered = 0;
+            let (flags, count) = match count {
+                0 => (MSG_DONTWAIT, DISPATCH_BATCH),
+                _ => (0, count)
+            };
+
+            while delivered < count {
+                match self.read_packet(flags) {
+                    Ok(packet) => {
+                        callback(packet);
+                        delivered += 1;
+                    }
+                    Err(e) if matches!(e.kind(), io::ErrorKind::InvalidData | io::ErrorKind::Interrupted) => {}
+                    Err(e) if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => break,
+                    Err(e) => return Err(e)
+                }
+            }
+
+            Ok(delivered)
+        }
+
+        //READS ON ITS OWN THREAD UNTIL STOPPED, THE CHANNEL IS UNBOUNDED SO A SLOW RECEIVER GROWS IT INSTEAD OF DROPPING
+        pub fn spawn_capture(self) -> io::Result<(Receiver<io::Result<Packet>>, CaptureHandle)> {
+            let (sender, receiver) = mpsc::channel();
+            let handle = CaptureHandle::spawn(self, sender)?;
+            Ok((receiver, handle))
+        }
+
+        //THE KERNEL RESETS ITS COUNTERS EVERY TIME THEY'RE READ SO THEY'RE ADDED UP HERE
+        pub fn stats(&mut self) -> io::Result<CaptureStats> {
+            let mut stats = TPacketStatsV3 {
+                tp_packets: 0,
+                tp_drops: 0,
+                tp_freeze_q_cnt: 0
+            };
+            let mut len = mem::size_of::<TPacketStatsV3>() as u32;
+
+            unsafe {
+                syscall(SYS_GET_SOCK_OPT, self.fd as i64, SOL_PACKET, PACKET_STATISTICS, &mut stats as *mut _ as i64, &mut len as *mut _ as i64)?
+            };
+
+            self.stats.add_kernel(stats.tp_packets, stats.tp_drops, stats.tp_freeze_q_cnt);
+            self.stats.set_interface_dropped(self.read_interface_dropped().saturating_sub(self.interface_dropped));
+
+            Ok(self.stats.clone())
+        }
+
+        //DRIVER LEVEL DROPS SINCE OPEN, 0 IF SYSFS ISN'T AVAILABLE
+        fn read_interface_dropped(&self) -> u64 {
+            fs::read_to_string(format!("/sys/class/net/{}/statistics/rx_dropped", self.device.get_name()))
+                .ok()
+                .and_then(|s| s.trim().parse().ok())
+                .unwrap_or(0)
+        }
+
+        //RECEIVES INTO A CALLER OWNED BUFFER WITHOUT ALLOCATING OR DECODING, PAIR WITH THE VIEW TYPES
+        //FRAMES LONGER THAN THE BUFFER OR SNAP LENGTH ARE CUT, THE INFO STILL HAS THE WIRE LENGTH
+        pub fn next_frame<'a>(&mut self, buffer: &'a mut [u8]) -> io::Result<(FrameInfo, &'a [u8])> {
+            self.recv_frame(buffer, 0)
+        }
+
+        fn recv_frame<'a>(&mut self, buffer: &'a mut [u8], flags: i64) -> io::Result<(FrameInfo, &'a [u8])> {
+            let captured = buffer.len().min(self.snap_length as usize);
+
+            let mut iov = IoVec {
+                iov_base: buffer.as_mut_ptr(),
+                iov_len: captured
+            };
+
+            let mut sockaddr = SockAddrLl {
+                sll_family: 0,
+                sll_protocol: 0,
+                sll_ifindex: 0,
+                sll_hatype: 0,
+                sll_pkttype: 0,
+                sll_halen: 0,
+                sll_addr: [0; 8],
+            };
+
+            let mut control = [0u64; 16];
+            let mut msg = MsgHdr {
+                msg_name: &mut sockaddr as *mut _ as *mut u8,
+                msg_namelen: mem::size_of::<SockAddrLl>() as u32,
+                msg_iov: &mut iov,
+                msg_iovlen: 1,
+                msg_control: control.as_mut_ptr() as *mut u8,
+                msg_controllen: mem::size_of_val(&control),
+                msg_flags: 0
+            };
+
+            let len = unsafe {
+                syscall(SYS_RECV_MSG, self.fd as i64, &mut msg as *mut _ as i64, MSG_TRUNC | flags, 0, 0)?
+            };
+
+            let control = unsafe {
+                slice::from_raw_parts(control.as_ptr() as *const u8, msg.msg_controllen)
+            };
+
+            let frame_time = match Self::read_timestamp(control) {
+                Some(frame_time) => frame_time,
+                None => SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos()).unwrap_or(0)
+            };
+
+            //MSG_TRUNC MAKES THE KERNEL RETURN THE WIRE LENGTH EVEN WHEN THE BUFFER WAS SHORTER
+            let mut original_length = len as usize;
+            let mut captured = original_length.min(captured);
+            let vlan = Self::read_vlan(control);
+
+            //THE TAG GOES BACK AFTER THE MAC ADDRESSES, THE LAST 4 BYTES ARE LOST IF THE BUFFER IS FULL
+            if let Some((tpid, tci)) = vlan {
+                if captured >= 12 && buffer.len() >= 16 {
+                    let end = captured.min(buffer.len() - 4);
+                    buffer.copy_within(12..end, 16);
+                    buffer[12..14].copy_from_slice(&tpid.to_be_bytes());
+                    buffer[14..16].copy_from_slice(&tci.to_be_bytes());
+                    captured = end + 4;
+                    original_length += 4;
+                }
+            }
+
+            let mut info = FrameInfo::new(frame_time, original_length, PacketTypes::from_code(sockaddr.sll_pkttype), sockaddr.sll_ifindex, sockaddr.sll_hatype);
+            info.set_vlan(vlan);
+
+            Ok((info, &buffer[..captured]))
+        }
+
+        //LEVEL, TYPE AND DATA OF EVERY cmsghdr IN A CONTROL BUFFER
+        fn control_messages(control: &[u8]) -> Vec<(i32, i32, &[u8])> {
+            let mut messages = Vec::new();
+            let mut offset = 0;
+
+            while offset + CMSG_HEADER_SIZE <= control.len() {
+                let len = usize::from_ne_bytes(control[offset..offset + CMSG_ALIGN].try_into().unwrap());
+                let level = i32::from_ne_bytes(control[offset + CMSG_ALIGN..offset + CMSG_ALIGN + 4].try_into().unwrap());
+                let _type = i32::from_ne_bytes(control[offset + CMSG_ALIGN + 4..offset + CMSG_HEADER_SIZE].try_into().unwrap());
+
+                if len < CMSG_HEADER_SIZE || offset + len > control.len() {
+                    break;
+                }
+
+                messages.push((level, _type, &control[offset + CMSG_HEADER_SIZE..offset + len]));
+                offset += (len + CMSG_ALIGN - 1) & !(CMSG_ALIGN - 1);
+            }
+
+            messages
+        }
+
+        /*
+        SCM_TIMESTAMPNS carries one timespec, SCM_TIMESTAMPING carries three: software, legacy and raw hardware.
+        Raw hardware is zero when the NIC didn't stamp the frame so software is used instead.
+        */
+        fn read_timestamp(control: &[u8]) -> Option<u128> {
+            for (level, _type, data) in Self::control_messages(control) {
+                if level != SOL_SOCKET as i32 {
+                    continue;
+                }
+
+                match _type {
+                    SCM_TIMESTAMPNS if data.len() >= TIMESPEC_SIZE => {
+                        return Some(Self::read_timespec(data));
+                    }
+                    SCM_TIMESTAMPING if data.len() >= 3 * TIMESPEC_SIZE => {
+                        return match Self::read_timespec(&data[2 * TIMESPEC_SIZE..]) {
+                            0 => Some(Self::read_timespec(data)),
+                            hardware => Some(hardware)
+                        };
+                    }
+                    _ => {}
+                }
+            }
+
+            None
+        }
+
+        //tpacket_auxdata, THE TPID IS ONLY REPORTED BY NEWER KERNELS SO 802.1Q IS ASSUMED WITHOUT IT
+        fn read_vlan(control: &[u8]) -> Option<(u16, u16)> {
+            for (level, _type, data) in Self::control_messages(control) {
+                if level != SOL_PACKET as i32 || _type != PACKET_AUXDATA as i32 || data.len() < 20 {
+                    continue;
+                }
+
+                let status = u32::from_ne_bytes(data[0..4].try_into().unwrap());
+                if status & TP_STATUS_VLAN_VALID == 0 {
+                    return None;
+                }
+
+                let tci = u16::from_ne_bytes([data[16], data[17]]);
+                let tpid = match status & TP_STATUS_VLAN_TPID_VALID {
+                    0 => ETH_P_8021Q,
+                    _ => u16::from_ne_bytes([data[18], data[19]])
+                };
+
+                return Some((tpid, tci));
+            }
+
+            None
+        }
+
+        fn read_timespec(buf: &[u8]) -> u128 {
+            let half = TIMESPEC_SIZE / 2;
+            let seconds = isize::from_ne_bytes(buf[0..half].try_into().unwrap());
+            let nanoseconds = isize::from_ne_bytes(buf[half..TIMESPEC_SIZE].try_into().unwrap());
+            seconds as u128 * 1_000_000_000 + nanoseconds as u128
+        }
+    }
+
+    impl Drop for Capture {
+
+        fn drop(&mut self) {
+            self.close().ok();
+        }
+    }
+
+    impl AsRawFd for Capture {
+
+        fn as_raw_fd(&self) -> RawFd {
+            self.fd
+        }
+    }
+
+    impl AsFd for Capture {
+
+        fn as_fd(&self) -> BorrowedFd<'_> {
+            unsafe {
+                BorrowedFd::borrow_raw(self.fd)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::os::fd::RawFd;
+    use std::sync::{mpsc, Arc};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    #[cfg(feature = "async")]
+    use std::sync::atomic::AtomicUsize;
+    #[cfg(feature = "async")]
+    use std::task::{Context, Poll, Wake, Waker};
+    use std::time::{Duration, Instant};
+    use crate::bpf::bpf_program::BpfProgram;
+    use crate::capture::{Capture, DISPATCH_BATCH, MSG_DONTWAIT};
+    use crate::capture::capture_handle::STOP_CHECK_INTERVAL;
+    use crate::capture::capture_stats::CaptureStats;
+    #[cfg(feature = "async")]
+    use crate::capture::reactor::Reactor;
+    use crate::capture::sys::syscall::{syscall, syscall6, SYS_CLOSE, SYS_SENDTO, SYS_SOCKETPAIR};
+    use crate::devices::Device;
+    use crate::packet::inter::decode_error::DecodeError;
+    use crate::packet::inter::interfaces::Interfaces;
+    use crate::packet::packet::Packet;
+    use crate::packet::layers::ethernet_frame::arp::arp_extension::ArpExtension;
+    use crate::packet::layers::ethernet_frame::ethernet_frame::EthernetFrame;
+    use crate::packet::layers::ethernet_frame::inter::ethernet_address::EthernetAddress;
+    use crate::packet::layers::ethernet_frame::inter::types::Types;
+    use crate::packet::layers::inter::layer::Layer;
+    use crate::packet::layers::raw::raw_layer::RawLayer;
+    use crate::pcap::inter::timestamp_precisions::TimestampPrecisions;
+    use crate::pcap::pcap_reader::PcapReader;
+    use crate::pcap::pcap_writer::PcapWriter;
+    use crate::pcapng::blocks::interface_description_block::{InterfaceDescriptionBlock, IF_TSRESOL};
+    use crate::pcapng::blocks::interface_statistics_block::{InterfaceStatisticsBlock, ISB_IF_DROP, ISB_IF_RECV};
+    use crate::pcapng::blocks::name_resolution_block::NameResolutionBlock;
+    use crate::pcapng::inter::block_option::BlockOption;
+    use crate::pcapng::inter::blocks::Blocks;
+    use crate::pcapng::pcapng_reader::PcapNgReader;
+    use crate::pcapng::pcapng_writer::PcapNgWriter;
+
+    const ARP_FRAME: [u8; 42] = [
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x08, 0x06,
+        0x00, 0x01, 0x08, 0x00, 0x06, 0x04, 0x00, 0x01, 0x00, 0x11, 0x22, 0x33, 0x44, 0x55,
+        0xc0, 0xa8, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xc0, 0xa8, 0x00, 0x02
+    ];
+
+    const AF_UNIX: i64 = 1;
+    const SOCK_DGRAM: i64 = 2;
+
+    //A SOCKETPAIR STANDS IN FOR THE PACKET SOCKET SO THE READ PATHS RUN WITHOUT CAP_NET_RAW, FRAMES GO IN THE OTHER END
+    fn socketpair_capture() -> (Capture, RawFd) {
+        let mut fds = [0 as RawFd; 2];
+        unsafe {
+            syscall(SYS_SOCKETPAIR, AF_UNIX, SOCK_DGRAM, 0, fds.as_mut_ptr() as i64, 0)
+        }.unwrap();
+
+        let device = Device::list().unwrap().remove(0);
+        (Capture::from_fd(&device, fds[0]), fds[1])
+    }
+
+    fn send_frame(fd: RawFd, frame: &[u8]) {
+        unsafe {
+            syscall6(SYS_SENDTO, fd as i64, frame.as_ptr() as i64, frame.len() as i64, 0, 0, 0)
+        }.unwrap();
+    }
+
+    fn close_fd(fd: RawFd) {
+        unsafe {
+            syscall(SYS_CLOSE, fd as i64, 0, 0, 0, 0)
+        }.unwrap();
+    }
+
+    //COUNTS WAKES SO THE REACTOR CAN BE CHECKED WITHOUT AN EXECUTOR
+    #[cfg(feature = "async")]
+    struct CountingWaker(AtomicUsize);
+
+    #[cfg(feature = "async")]
+    impl CountingWaker {
+
+        fn get_count(&self) -> usize {
+            self.0.load(Ordering::Acquire)
+        }
+
+        fn wait_for(&self, count: usize) -> bool {
+            let start = Instant::now();
+
+            while self.get_count() < count {
+                if start.elapsed() > Duration::from_secs(5) {
+                    return false;
+                }
+
+                std::thread::sleep(Duration::from_millis(5));
+            }
+
+            true
+        }
+    }
+
+    #[cfg(feature = "async")]
+    impl Wake for CountingWaker {
+
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::AcqRel);
+        }
+    }
+
+    /*
+    update values of dhcp layer
+    try not to use vec::new
+    frame should calculate length on the spot...
+
+    - Honestly redo all of the packet class
+    - IE length, interface, and frame time may not be needed...
+    */
+
+    #[test]
+    fn test() {
+        let devices = Device::list().unwrap();
+        println!("{:?}", devices);
+
+        let device = devices.into_iter().find(|d| d.get_name().contains("Ethernet adapter Ethernet:")).unwrap();
+        println!("{:?}", device);
+
+        let mut cap = Capture::from_device(&device).unwrap();
+        cap.open().unwrap();
+
+
+        while let Ok(packet) = cap.next_packet() {
+            println!("{:x?}", packet);
+        }
+    }
+
+    #[test]
+    fn pcap_writer() {
+        let packet = Packet::new(Interfaces::Ethernet, 1_700_000_000_123_456_789, &ARP_FRAME).unwrap();
+
+        let mut writer = PcapWriter::new(Vec::new(), Interfaces::Ethernet, TimestampPrecisions::Nano).unwrap();
+        writer.write_packet(&packet).unwrap();
+        let buf = writer.into_inner();
+
+        assert_eq!(buf.len(), 24 + 16 + ARP_FRAME.len());
+        assert_eq!(&buf[0..4], &0xa1b23c4du32.to_le_bytes());
+        assert_eq!(&buf[20..24], &1u32.to_le_bytes());
+        assert_eq!(&buf[24..28], &1_700_000_000u32.to_le_bytes());
+        assert_eq!(&buf[28..32], &123_456_789u32.to_le_bytes());
+        assert_eq!(&buf[40..], &ARP_FRAME);
+    }
+
+    #[test]
+    fn pcap_reader() {
+        let packet = Packet::new(Interfaces::Ethernet, 1_700_000_000_123_456_789, &ARP_FRAME).unwrap();
+
+        let mut writer = PcapWriter::new(Vec::new(), Interfaces::Ethernet, TimestampPrecisions::Micro).unwrap();
+        writer.write_packet(&packet).unwrap();
+        writer.write_packet(&packet).unwrap();
+
+        let buf = writer.into_inner();
+        let reader = PcapReader::new(buf.as_slice()).unwrap();
+        assert_eq!(reader.get_interface(), Interfaces::Ethernet);
+        assert_eq!(reader.get_precision(), TimestampPrecisions::Micro);
+
+        let packets = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(packets.len(), 2);
+        assert_eq!(packets[0].get_frame_time(), 1_700_000_000_123_456_000);
+        assert_eq!(packets[1].to_bytes(), ARP_FRAME);
+
+        //F BIT AND A 4 BYTE FCS ON TOP OF LINKTYPE_ETHERNET
+        let mut buf = buf;
+        buf[20..24].copy_from_slice(&0x24000001u32.to_le_bytes());
+        let reader = PcapReader::new(buf.as_slice()).unwrap();
+        assert_eq!(reader.get_interface(), Interfaces::Ethernet);
+        assert_eq!(reader.get_fcs_length(), Some(4));
+    }
+
+    #[test]
+    fn reader_filter() {
+        let arp = Packet::new(Interfaces::Ethernet, 0, &ARP_FRAME).unwrap();
+        let mut lldp = ARP_FRAME;
+        lldp[12] = 0x88;
+        lldp[13] = 0xcc;
+        let lldp = Packet::new(Interfaces::Ethernet, 0, &lldp).unwrap();
+
+        let mut writer = PcapWriter::new(Vec::new(), Interfaces::Ethernet, TimestampPrecisions::Micro).unwrap();
+        writer.write_packet(&arp).unwrap();
+        writer.write_packet(&lldp).unwrap();
+        writer.write_packet(&arp).unwrap();
+        let buf = writer.into_inner();
+
+        let mut reader = PcapReader::new(buf.as_slice()).unwrap();
+        reader.set_filter("ether proto 0x88cc").unwrap();
+        assert_eq!(reader.collect::<Result<Vec<_>, _>>().unwrap().len(), 1);
+
+        let mut reader = PcapReader::new(buf.as_slice()).unwrap();
+        reader.set_program(BpfProgram::compile_with_snap_length("arp", 20).unwrap()).unwrap();
+        let packets = (0..2).map(|_| reader.next_record().unwrap().unwrap().1).collect::<Vec<_>>();
+        assert_eq!(packets, vec![ARP_FRAME[..20].to_vec(), ARP_FRAME[..20].to_vec()]);
+        assert!(reader.next_record().unwrap().is_none());
+
+        let mut writer = PcapNgWriter::new(Vec::new()).unwrap();
+        writer.add_interface(InterfaceDescriptionBlock::new(Interfaces::Ethernet, 0)).unwrap();
+        writer.write_packet(0, &lldp).unwrap();
+        writer.write_packet(0, &arp).unwrap();
+        let buf = writer.into_inner();
+
+        let mut reader = PcapNgReader::new(buf.as_slice()).unwrap();
+        reader.set_filter("arp").unwrap();
+        let packets = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(packets.len(), 1);
+        assert_eq!(packets[0].1.to_bytes(), ARP_FRAME);
+
+        let mut reader = PcapNgReader::new(buf.as_slice()).unwrap();
+        assert!(reader.set_filter("arp port 80").is_err());
+        assert!(reader.get_filter().is_none());
+    }
+
+    #[test]
+    fn pcapng_round_trip() {
+        let packet = Packet::new(Interfaces::Ethernet, 1_700_000_000_123_456_000, &ARP_FRAME).unwrap();
+
+        let mut writer = PcapNgWriter::new(Vec::new()).unwrap();
+        assert_eq!(writer.add_interface(InterfaceDescriptionBlock::new(Interfaces::Ethernet, 0)).unwrap(), 0);
+        assert_eq!(writer.add_interface(InterfaceDescriptionBlock::new(Interfaces::Ethernet, 16)).unwrap(), 1);
+
+        let mut names = NameResolutionBlock::new();
+        names.add_record("192.168.0.1".parse().unwrap(), vec!["gateway".to_string()]);
+        writer.write_name_resolution(&names).unwrap();
+
+        writer.write_packet_with_comment(0, &packet, "first").unwrap();
+        writer.write_packet(1, &packet).unwrap();
+        writer.write_simple_packet(&packet).unwrap();
+
+        let mut statistics = InterfaceStatisticsBlock::new(1, 0);
+        statistics.set_counter(ISB_IF_RECV, 2);
+        statistics.set_counter(ISB_IF_DROP, 0);
+        writer.write_statistics(&statistics).unwrap();
+
+        let buf = writer.into_inner();
+        let mut reader = PcapNgReader::new(buf.as_slice()).unwrap();
+
+        let mut blocks = Vec::new();
+        while let Some(block) = reader.next_block().unwrap() {
+            blocks.push(block);
+        }
+
+        assert_eq!(reader.get_interfaces().len(), 2);
+        assert_eq!(blocks.len(), 7);
+
+        match &blocks[2] {
+            Blocks::NameResolution(block) => assert_eq!(block.get_records(), names.get_records()),
+            _ => panic!("expected name resolution block")
+        }
+
+        match &blocks[3] {
+            Blocks::EnhancedPacket(block) => {
+                assert_eq!(block.get_comments(), vec!["first".to_string()]);
+                assert_eq!(block.get_data(), &ARP_FRAME);
+            }
+            _ => panic!("expected enhanced packet block")
+        }
+
+        match &blocks[4] {
+            Blocks::EnhancedPacket(block) => {
+                assert_eq!(block.get_interface_id(), 1);
+                assert_eq!(block.get_data().len(), 16);
+                assert_eq!(block.get_original_length() as usize, ARP_FRAME.len());
+            }
+            _ => panic!("expected enhanced packet block")
+        }
+
+        match &blocks[6] {
+            Blocks::InterfaceStatistics(block) => assert_eq!(block.get_received(), Some(2)),
+            _ => panic!("expected interface statistics block")
+        }
+
+        let reader = PcapNgReader::new(buf.as_slice()).unwrap();
+        let packets = reader.take(1).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(packets[0].0, 0);
+        assert_eq!(packets[0].1.get_frame_time(), 1_700_000_000_123_456_000);
+        assert_eq!(packets[0].1.to_bytes(), ARP_FRAME);
+
+        //BOTH PACKET BLOCKS MUST MATCH THE LINK TYPE OF THE INTERFACE THEY'RE WRITTEN UNDER
+        let mut writer = PcapNgWriter::new(Vec::new()).unwrap();
+        writer.add_interface(InterfaceDescriptionBlock::new(Interfaces::WiFi, 0)).unwrap();
+        assert_eq!(writer.write_packet(0, &packet).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(writer.write_simple_packet(&packet).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn pcapng_timestamp_resolution() {
+        let mut interface = InterfaceDescriptionBlock::new(Interfaces::Ethernet, 0);
+        interface.add_option(BlockOption::new(IF_TSRESOL, vec![60]));
+
+        let mut writer = PcapNgWriter::new(Vec::new()).unwrap();
+        assert_eq!(writer.add_interface(interface.clone()).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(writer.write_block(&Blocks::InterfaceDescription(interface.clone())).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+        assert!(writer.get_interfaces().is_empty());
+
+        let mut buf = writer.into_inner();
+        buf.extend(interface.to_bytes());
+        let err = PcapNgReader::new(buf.as_slice()).and_then(|mut reader| reader.next_packet().map(|_| ())).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let mut interface = InterfaceDescriptionBlock::new(Interfaces::Ethernet, 0);
+        interface.add_option(BlockOption::new(IF_TSRESOL, vec![38]));
+        assert_eq!(interface.get_timestamp_units(), 10u128.pow(38));
+    }
+
+    #[test]
+    fn decode_errors() {
+        assert_eq!(Packet::new(Interfaces::WiFi, 0, &ARP_FRAME).unwrap_err(), DecodeError::UnknownCode {
+            layer: "Packet",
+            offset: 0,
+            code: Interfaces::WiFi.get_code()
+        });
+
+        let mut arp = ARP_FRAME[14..].to_vec();
+        arp[5] = 16;
+        assert_eq!(ArpExtension::from_bytes(&arp).unwrap_err(), DecodeError::BadLength {
+            layer: "ARP",
+            offset: 5,
+            length: 16
+        });
+
+        let error = Packet::new(Interfaces::Ethernet, 0, &ARP_FRAME[..30]).unwrap_err();
+        assert_eq!(error.get_layer(), "ARP");
+        assert_eq!(error.get_offset(), 14);
+        assert!(matches!(error, DecodeError::Truncated { needed: 28, available: 16, .. }));
+    }
+
+    #[test]
+    fn capture_stats() {
+        let mut stats = CaptureStats::new();
+        stats.add_kernel(10, 2, 1);
+        stats.add_kernel(5, 0, 0);
+        stats.record_decode_error(&Packet::new(Interfaces::Ethernet, 0, &ARP_FRAME[..30]).unwrap_err());
+        stats.record_decode_error(&Packet::new(Interfaces::Ethernet, 0, &ARP_FRAME[..10]).unwrap_err());
+        stats.record_decode_error(&Packet::new(Interfaces::Ethernet, 0, &ARP_FRAME[..20]).unwrap_err());
+
+        assert_eq!(stats.get_received(), 15);
+        assert_eq!(stats.get_dropped(), 2);
+        assert_eq!(stats.get_freeze_count(), 1);
+        assert_eq!(stats.get_decode_errors_for("ARP"), 2);
+        assert_eq!(stats.get_decode_errors_for("TCP"), 0);
+        assert_eq!(stats.get_total_decode_errors(), 3);
+
+        let block = stats.to_interface_statistics_block(0, 0);
+        assert_eq!(block.get_received(), Some(15));
+        assert_eq!(block.get_dropped(), Some(0));
+    }
+
+    #[test]
+    fn syscall_errno() {
+        let err = unsafe {
+            syscall(SYS_CLOSE, -1, 0, 0, 0, 0)
+        }.unwrap_err();
+
+        assert_eq!(err.raw_os_error(), Some(9));
+    }
+
+    #[test]
+    fn raw_fallback() {
+        let mut frame = ARP_FRAME.to_vec();
+        frame[12] = 0x88;
+        frame[13] = 0xcc;
+
+        let packet = Packet::new(Interfaces::Ethernet, 0, &frame).unwrap();
+        let ethernet = packet.get_frame().as_any().downcast_ref::<EthernetFrame>().unwrap();
+        assert_eq!(ethernet.get_type(), Types::Unknown(0x88cc));
+
+        let raw = ethernet.get_data().unwrap().as_any().downcast_ref::<RawLayer>().unwrap();
+        assert_eq!(raw.get_code(), 0x88cc);
+        assert_eq!(raw.get_data(), &frame[14..]);
+        assert_eq!(packet.to_bytes(), frame);
+    }
+
+    #[test]
+    fn packets_iterator() {
+        let (mut capture, peer) = socketpair_capture();
+        capture.set_nonblocking(true).unwrap();
+
+        send_frame(peer, &ARP_FRAME);
+        send_frame(peer, &ARP_FRAME[..30]);
+        send_frame(peer, &ARP_FRAME);
+
+        //DECODE ERRORS ARE HANDED OUT WITHOUT ENDING IT, NOTHING LEFT TO READ DOES
+        let packets: Vec<_> = capture.packets().collect();
+        assert_eq!(packets.len(), 3);
+        assert!(packets[0].as_ref().unwrap().layer::<ArpExtension>().is_some());
+        assert_eq!(packets[1].as_ref().unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert_eq!(packets[2].as_ref().unwrap().to_bytes(), ARP_FRAME);
+
+        capture.set_nonblocking(false).unwrap();
+        capture.set_read_timeout(Some(Duration::from_millis(10))).unwrap();
+        assert!(capture.packets().next().is_none());
+
+        close_fd(peer);
+    }
+
+    #[test]
+    fn dispatch() {
+        let (mut capture, peer) = socketpair_capture();
+
+        for _ in 0..3 {
+            send_frame(peer, &ARP_FRAME);
+        }
+        send_frame(peer, &ARP_FRAME[..30]);
+
+        let mut packets = Vec::new();
+        assert_eq!(capture.dispatch(2, |packet| packets.push(packet)).unwrap(), 2);
+
+        //BLOCKING WITH NO TIMEOUT, 0 STILL RETURNS ONCE THE QUEUE IS EMPTY AND SKIPS THE FRAME THAT DOESN'T DECODE
+        assert_eq!(capture.get_read_timeout().unwrap(), None);
+        assert_eq!(capture.dispatch(0, |packet| packets.push(packet)).unwrap(), 1);
+        assert_eq!(capture.dispatch(0, |packet| packets.push(packet)).unwrap(), 0);
+        assert_eq!(packets.len(), 3);
+        assert!(!capture.is_nonblocking().unwrap());
+
+        //A WRITER THAT KEEPS THE QUEUE FULL DOESN'T KEEP 0 GOING FOREVER
+        let stop = Arc::new(AtomicBool::new(false));
+        let writer_stop = stop.clone();
+        let writer = std::thread::spawn(move || {
+            while !writer_stop.load(Ordering::Acquire) {
+                unsafe {
+                    syscall6(SYS_SENDTO, peer as i64, ARP_FRAME.as_ptr() as i64, ARP_FRAME.len() as i64, MSG_DONTWAIT, 0, 0).ok();
+                }
+            }
+        });
+
+        assert!(capture.wait(Some(Duration::from_secs(5))).unwrap());
+        let (sender, receiver) = mpsc::channel();
+        let reader = std::thread::spawn(move || {
+            sender.send(capture.dispatch(0, |_| {})).ok();
+        });
+
+        let delivered = receiver.recv_timeout(Duration::from_secs(10));
+        stop.store(true, Ordering::Release);
+        writer.join().unwrap();
+        reader.join().unwrap();
+
+        let delivered = delivered.unwrap().unwrap();
+        assert!(delivered > 0 && delivered <= DISPATCH_BATCH);
+
+        close_fd(peer);
+    }
+
+    #[test]
+    fn try_clone_memberships() {
+        let (mut capture, peer) = socketpair_capture();
+        let address = EthernetAddress::new(0x01, 0x00, 0x5e, 0x00, 0x00, 0x01);
+        capture.set_promiscuous_mode(true).unwrap();
+        capture.set_all_multicast(true).unwrap();
+        capture.add_multicast(address).unwrap();
+
+        //THE CLONE HAS NONE OF ITS OWN SO TURNING THEM OFF THERE LEAVES THE ORIGINAL'S IN PLACE
+        let mut clone = capture.try_clone().unwrap();
+        assert!(!clone.is_promiscuous_mode());
+        assert!(!clone.is_all_multicast());
+        assert!(clone.get_multicast().is_empty());
+
+        clone.set_promiscuous_mode(false).unwrap();
+        clone.remove_multicast(address).unwrap();
+        assert!(capture.is_promiscuous_mode());
+        assert!(capture.is_all_multicast());
+        assert_eq!(capture.get_multicast(), &vec![address]);
+
+        clone.set_promiscuous_mode(true).unwrap();
+        capture.set_promiscuous_mode(false).unwrap();
+        assert!(clone.is_promiscuous_mode());
+
+        close_fd(peer);
+    }
+
+    #[test]
+    fn capture_handle() {
+        let (capture, peer) = socketpair_capture();
+        let (receiver, handle) = capture.spawn_capture().unwrap();
+
+        send_frame(peer, &ARP_FRAME);
+        send_frame(peer, &ARP_FRAME[..30]);
+        send_frame(peer, &ARP_FRAME);
+
+        assert!(receiver.recv_timeout(Duration::from_secs(5)).unwrap().is_ok());
+        assert_eq!(receiver.recv_timeout(Duration::from_secs(5)).unwrap().unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert!(receiver.recv_timeout(Duration::from_secs(5)).unwrap().is_ok());
+        assert!(!handle.is_finished());
+
+        //STOP JOINS THE THREAD, ITS SENDER IS GONE AND THE CAPTURE COMES BACK STILL OPEN
+        let start = Instant::now();
+        let mut capture = handle.stop();
+        assert!(start.elapsed() < STOP_CHECK_INTERVAL * 10);
+        assert!(receiver.recv().is_err());
+
+        send_frame(peer, &ARP_FRAME);
+        capture.set_nonblocking(true).unwrap();
+        assert!(capture.next_packet().is_ok());
+
+        //DROPPING THE RECEIVER ENDS THE THREAD ON THE NEXT PACKET
+        let (receiver, handle) = capture.spawn_capture().unwrap();
+        drop(receiver);
+        send_frame(peer, &ARP_FRAME);
+
+        let start = Instant::now();
+        while !handle.is_finished() {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        drop(handle);
+        close_fd(peer);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn reactor() {
+        let (mut capture, peer) = socketpair_capture();
+        let fd = capture.get_fd();
+
+        let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        let waker = Waker::from(counter.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        //NOTHING QUEUED SO THE WAKER IS REGISTERED AND LEFT ALONE
+        assert!(capture.poll_next_packet(&mut cx).is_pending());
+        let reactor = Reactor::get_if_running().unwrap();
+        assert!(reactor.is_registered(fd));
+        std::thread::sleep(Duration::from_millis(50));
+        assert_eq!(counter.get_count(), 0);
+
+        send_frame(peer, &ARP_FRAME);
+        assert!(counter.wait_for(1));
+
+        //ONE SHOT, STILL READABLE BUT NOT WOKEN AGAIN UNTIL A POLL ARMS IT
+        send_frame(peer, &ARP_FRAME);
+        std::thread::sleep(Duration::from_millis(50));
+        assert_eq!(counter.get_count(), 1);
+
+        assert!(matches!(capture.poll_next_packet(&mut cx), Poll::Ready(Ok(_))));
+        assert!(matches!(capture.poll_next_packet(&mut cx), Poll::Ready(Ok(_))));
+        assert!(capture.poll_next_packet(&mut cx).is_pending());
+
+        send_frame(peer, &ARP_FRAME);
+        assert!(counter.wait_for(2));
+
+        //ARMED AGAIN WHILE READABLE WAKES STRAIGHT AWAY
+        reactor.register(fd, &waker).unwrap();
+        assert!(counter.wait_for(3));
+
+        //DEREGISTERED, A NEW PACKET WAKES NOBODY
+        assert!(matches!(capture.poll_next_packet(&mut cx), Poll::Ready(Ok(_))));
+        assert!(capture.poll_next_packet(&mut cx).is_pending());
+        reactor.deregister(fd);
+        assert!(!reactor.is_registered(fd));
+
+        send_frame(peer, &ARP_FRAME);
+        std::thread::sleep(Duration::from_millis(50));
+        assert_eq!(counter.get_count(), 3);
+
+        //CLOSING TAKES IT OUT SO A DESCRIPTOR REUSING THE NUMBER STARTS FRESH
+        assert!(matches!(capture.poll_next_packet(&mut cx), Poll::Ready(Ok(_))));
+        assert!(capture.poll_next_packet(&mut cx).is_pending());
+        assert!(reactor.is_registered(fd));
+        drop(capture);
+        assert!(!reactor.is_registered(fd));
+
+        close_fd(peer);
     }
 }
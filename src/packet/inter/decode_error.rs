@@ -0,0 +1,92 @@
+use std::{fmt, io};
+use std::error::Error;
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum DecodeError {
+    Truncated {
+        layer: &'static str,
+        offset: usize,
+        needed: usize,
+        available: usize
+    },
+    UnknownCode {
+        layer: &'static str,
+        offset: usize,
+        code: u32
+    },
+    BadLength {
+        layer: &'static str,
+        offset: usize,
+        length: usize
+    },
+    //ONLY FROM Packet::validate, PLAIN DECODING LEAVES CHECKSUMS ALONE
+    BadChecksum {
+        layer: &'static str,
+        offset: usize,
+        expected: u16,
+        found: u16
+    }
+}
+
+impl DecodeError {
+
+    pub fn get_layer(&self) -> &'static str {
+        match self {
+            Self::Truncated { layer, .. } |
+            Self::UnknownCode { layer, .. } |
+            Self::BadLength { layer, .. } |
+            Self::BadChecksum { layer, .. } => layer
+        }
+    }
+
+    //BYTE OFFSET FROM THE START OF THE FRAME
+    pub fn get_offset(&self) -> usize {
+        match self {
+            Self::Truncated { offset, .. } |
+            Self::UnknownCode { offset, .. } |
+            Self::BadLength { offset, .. } |
+            Self::BadChecksum { offset, .. } => *offset
+        }
+    }
+
+    //LAYERS ONLY SEE THEIR OWN SLICE, PARENTS SHIFT THE OFFSET BY THEIR HEADER SIZE
+    pub fn shift(mut self, by: usize) -> Self {
+        match &mut self {
+            Self::Truncated { offset, .. } |
+            Self::UnknownCode { offset, .. } |
+            Self::BadLength { offset, .. } |
+            Self::BadChecksum { offset, .. } => *offset += by
+        }
+
+        self
+    }
+}
+
+impl fmt::Display for DecodeError {
+
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated { layer, offset, needed, available } => {
+                write!(f, "{} truncated at offset {}: needed {} bytes, {} available", layer, offset, needed, available)
+            }
+            Self::UnknownCode { layer, offset, code } => {
+                write!(f, "{} unknown code {:#x} at offset {}", layer, code, offset)
+            }
+            Self::BadLength { layer, offset, length } => {
+                write!(f, "{} bad length {} at offset {}", layer, length, offset)
+            }
+            Self::BadChecksum { layer, offset, expected, found } => {
+                write!(f, "{} bad checksum at offset {}: expected {:#06x}, found {:#06x}", layer, offset, expected, found)
+            }
+        }
+    }
+}
+
+impl Error for DecodeError {}
+
+impl From<DecodeError> for io::Error {
+
+    fn from(error: DecodeError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, error)
+    }
+}
@@ -4,3 +4,33 @@ pub enum Interfaces {
     WiFi,
     Bluetooth
 }
+
+impl Interfaces {
+
+    pub fn from_code(code: u32) -> Result<Self, String> {
+        for c in [Self::Ethernet, Self::WiFi, Self::Bluetooth] {
+            if c.get_code() == code {
+                return Ok(c);
+            }
+        }
+
+        Err(format!("Couldn't find for code: {}", code))
+    }
+
+    pub fn get_code(&self) -> u32 {
+        match self {
+            Self::Ethernet => 1,
+            Self::WiFi => 105,
+            Self::Bluetooth => 187
+        }
+    }
+
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        match self {
+            Self::Ethernet => "Ethernet",
+            Self::WiFi => "IEEE 802.11",
+            Self::Bluetooth => "Bluetooth HCI H4"
+        }.to_string()
+    }
+}
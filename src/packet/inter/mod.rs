@@ -1 +1,3 @@
 pub mod interfaces;
+pub mod decode_error;
+pub mod packet_types;
@@ -0,0 +1,54 @@
+//sll_pkttype, WHO THE FRAME WAS ADDRESSED TO AND WHICH WAY IT WAS GOING
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum PacketTypes {
+    Host,
+    Broadcast,
+    Multicast,
+    OtherHost,
+    Outgoing,
+    Loopback,
+    Unknown(u8)
+}
+
+impl PacketTypes {
+
+    pub fn from_code(code: u8) -> Self {
+        for c in [Self::Host, Self::Broadcast, Self::Multicast, Self::OtherHost, Self::Outgoing, Self::Loopback] {
+            if c.get_code() == code {
+                return c;
+            }
+        }
+
+        Self::Unknown(code)
+    }
+
+    pub fn get_code(&self) -> u8 {
+        match self {
+            Self::Host => 0,
+            Self::Broadcast => 1,
+            Self::Multicast => 2,
+            Self::OtherHost => 3,
+            Self::Outgoing => 4,
+            Self::Loopback => 5,
+            Self::Unknown(code) => *code
+        }
+    }
+
+    //LOOPBACK IS THE ECHO OF A FRAME WE SENT, IT ARRIVES LIKE AN INCOMING ONE
+    pub fn is_outgoing(&self) -> bool {
+        matches!(self, Self::Outgoing)
+    }
+
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        match self {
+            Self::Host => "Host",
+            Self::Broadcast => "Broadcast",
+            Self::Multicast => "Multicast",
+            Self::OtherHost => "Other Host",
+            Self::Outgoing => "Outgoing",
+            Self::Loopback => "Loopback",
+            Self::Unknown(_) => "Unknown"
+        }.to_string()
+    }
+}
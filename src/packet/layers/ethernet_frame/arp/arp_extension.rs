@@ -1,12 +1,15 @@
 use std::any::Any;
 use std::fmt::Debug;
 use std::net::Ipv4Addr;
+use crate::packet::inter::decode_error::DecodeError;
 use crate::packet::layers::ethernet_frame::arp::inter::arp_operations::ArpOperations;
 use crate::packet::layers::ethernet_frame::inter::ethernet_address::EthernetAddress;
 use crate::packet::layers::ethernet_frame::inter::types::Types;
 use crate::packet::layers::inter::layer::Layer;
 
 const ARP_HEADER_LEN: usize = 28;
+const ETHERNET_ADDRESS_LEN: u8 = 6;
+const IPV4_ADDRESS_LEN: u8 = 4;
 
 #[derive(Clone, Debug)]
 pub struct ArpExtension {
@@ -27,8 +30,8 @@ impl ArpExtension {
         Self {
             hardware_type: 1,
             protocol_type: Types::IPv4,
-            hardware_size: 6,
-            protocol_size: 4,
+            hardware_size: ETHERNET_ADDRESS_LEN,
+            protocol_size: IPV4_ADDRESS_LEN,
             opcode,
             sender_mac,
             sender_address,
@@ -108,21 +111,47 @@ impl ArpExtension {
     pub fn get_target_address(&self) -> Ipv4Addr {
         self.target_address
     }
+
+    //ONLY MAC AND IPv4 ADDRESSES ARE DECODED, SHORT BUFFERS PASS SO from_bytes CAN REPORT THEM TRUNCATED
+    pub fn is_ethernet_ipv4(buf: &[u8]) -> bool {
+        buf.len() < 6 || (buf[4] == ETHERNET_ADDRESS_LEN && buf[5] == IPV4_ADDRESS_LEN)
+    }
 }
 
 impl Layer for ArpExtension {
 
-    fn from_bytes(buf: &[u8]) -> Option<Self> {
+    fn from_bytes(buf: &[u8]) -> Result<Self, DecodeError> {
         if buf.len() < ARP_HEADER_LEN {
-            return None;
+            return Err(DecodeError::Truncated {
+                layer: "ARP",
+                offset: 0,
+                needed: ARP_HEADER_LEN,
+                available: buf.len()
+            });
+        }
+
+        if buf[4] != ETHERNET_ADDRESS_LEN {
+            return Err(DecodeError::BadLength {
+                layer: "ARP",
+                offset: 4,
+                length: buf[4] as usize
+            });
+        }
+
+        if buf[5] != IPV4_ADDRESS_LEN {
+            return Err(DecodeError::BadLength {
+                layer: "ARP",
+                offset: 5,
+                length: buf[5] as usize
+            });
         }
 
-        Some(Self {
+        Ok(Self {
             hardware_type: u16::from_be_bytes([buf[0], buf[1]]),
-            protocol_type: Types::from_code(u16::from_be_bytes([buf[2], buf[3]])).unwrap(),
+            protocol_type: Types::from_code(u16::from_be_bytes([buf[2], buf[3]])),
             hardware_size: buf[4],
             protocol_size: buf[5],
-            opcode: ArpOperations::from_code(u16::from_be_bytes([buf[6], buf[7]])).unwrap(),
+            opcode: ArpOperations::from_code(u16::from_be_bytes([buf[6], buf[7]])),
             sender_mac: EthernetAddress::new(buf[8], buf[9], buf[10], buf[11], buf[12], buf[13]),
             sender_address: Ipv4Addr::new(buf[14], buf[15], buf[16], buf[17]),
             target_mac: EthernetAddress::new(buf[18], buf[19], buf[20], buf[21], buf[22], buf[23]),
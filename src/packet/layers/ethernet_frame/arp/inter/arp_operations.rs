@@ -4,19 +4,20 @@ pub enum ArpOperations {
     Reply,
     Rarp,
     Nak,
-    InArp
+    InArp,
+    Unknown(u16)
 }
 
 impl ArpOperations {
 
-    pub fn from_code(code: u16) -> Result<Self, String> {
+    pub fn from_code(code: u16) -> Self {
         for c in [Self::Request, Self::Reply, Self::Rarp, Self::Nak, Self::InArp] {
             if c.get_code() == code {
-                return Ok(c);
+                return c;
             }
         }
 
-        Err(format!("Couldn't find for code: {}", code))
+        Self::Unknown(code)
     }
 
     pub fn get_code(&self) -> u16 {
@@ -25,17 +26,20 @@ impl ArpOperations {
             Self::Reply => 2,
             Self::Rarp => 3,
             Self::Nak => 4,
-            Self::InArp => 15
+            Self::InArp => 8,
+            Self::Unknown(code) => *code
         }
     }
 
+    #[allow(clippy::inherent_to_string)]
     pub fn to_string(&self) -> String {
         match self {
             Self::Request => "ARP Request",
             Self::Reply => "ARP Reply",
             Self::Rarp => "RARP Request",
             Self::Nak => "ARP-NAK",
-            Self::InArp => "InARP Request"
+            Self::InArp => "InARP Request",
+            Self::Unknown(_) => "Unknown"
         }.to_string()
     }
 }
@@ -1,10 +1,13 @@
 use std::any::Any;
+use crate::packet::inter::decode_error::DecodeError;
 use crate::packet::layers::ethernet_frame::arp::arp_extension::ArpExtension;
 use crate::packet::layers::ethernet_frame::inter::ethernet_address::EthernetAddress;
 use crate::packet::layers::ethernet_frame::inter::types::Types;
 use crate::packet::layers::ethernet_frame::ip::ipv4_layer::Ipv4Layer;
 use crate::packet::layers::ethernet_frame::ip::ipv6_layer::Ipv6Layer;
+use crate::packet::layers::ethernet_frame::vlan::vlan_layer::VlanLayer;
 use crate::packet::layers::inter::layer::Layer;
+use crate::packet::layers::raw::raw_layer::RawLayer;
 
 const ETHERNET_FRAME_LEN: usize = 14;
 
@@ -14,6 +17,7 @@ pub struct EthernetFrame {
     source_mac: EthernetAddress,
     _type: Types,
     data: Option<Box<dyn Layer>>,
+    trailer: Vec<u8>,
     length: usize
 }
 
@@ -25,6 +29,7 @@ impl EthernetFrame {
             source_mac,
             _type,
             data: None,
+            trailer: Vec::new(),
             length: ETHERNET_FRAME_LEN
         }
     }
@@ -58,6 +63,7 @@ impl EthernetFrame {
         self.data = Some(data);
     }
 
+    #[allow(clippy::borrowed_box)]
     pub fn get_data(&self) -> Option<&Box<dyn Layer>> {
         self.data.as_ref()
     }
@@ -65,37 +71,65 @@ impl EthernetFrame {
     pub fn get_data_mut(&mut self) -> Option<&mut Box<dyn Layer>> {
         self.data.as_mut()
     }
+
+    //PADDING OR FCS BYTES FOLLOWING THE PAYLOAD
+    pub fn set_trailer(&mut self, trailer: Vec<u8>) {
+        self.trailer = trailer;
+    }
+
+    pub fn get_trailer(&self) -> &Vec<u8> {
+        &self.trailer
+    }
 }
 
 impl Layer for EthernetFrame {
 
-    fn from_bytes(buf: &[u8]) -> Option<Self> {
+    fn from_bytes(buf: &[u8]) -> Result<Self, DecodeError> {
         if buf.len() < ETHERNET_FRAME_LEN {
-            return None;
+            return Err(DecodeError::Truncated {
+                layer: "Ethernet",
+                offset: 0,
+                needed: ETHERNET_FRAME_LEN,
+                available: buf.len()
+            });
         }
 
-        let _type = Types::from_code(u16::from_be_bytes([buf[12], buf[13]])).unwrap();
+        let _type = Types::from_code(u16::from_be_bytes([buf[12], buf[13]]));
+
+        let payload = &buf[ETHERNET_FRAME_LEN..];
 
-        let data = match _type {
+        let data: Option<Box<dyn Layer>> = match _type {
+            _ if payload.is_empty() => {
+                None
+            }
             Types::IPv4 => {
-                Some(Ipv4Layer::from_bytes(&buf[ETHERNET_FRAME_LEN..])?.dyn_clone())
+                Some(Box::new(Ipv4Layer::from_bytes(payload).map_err(|e| e.shift(ETHERNET_FRAME_LEN))?))
             }
-            Types::Arp => {
-                Some(ArpExtension::from_bytes(&buf[ETHERNET_FRAME_LEN..])?.dyn_clone())
+            Types::Arp if ArpExtension::is_ethernet_ipv4(payload) => {
+                Some(Box::new(ArpExtension::from_bytes(payload).map_err(|e| e.shift(ETHERNET_FRAME_LEN))?))
             }
             Types::IPv6 => {
-                Some(Ipv6Layer::from_bytes(&buf[ETHERNET_FRAME_LEN..])?.dyn_clone())
+                Some(Box::new(Ipv6Layer::from_bytes(payload).map_err(|e| e.shift(ETHERNET_FRAME_LEN))?))
             }
-            Types::Broadcast => {
-                None
+            Types::Vlan | Types::QinQ => {
+                Some(Box::new(VlanLayer::from_bytes(payload).map_err(|e| e.shift(ETHERNET_FRAME_LEN))?))
+            }
+            Types::Arp | Types::Broadcast | Types::Unknown(_) => {
+                Some(Box::new(RawLayer::new(_type.get_code(), payload.to_vec())))
             }
         };
 
-        Some(Self {
+        let consumed = match &data {
+            Some(layer) => layer.len().min(payload.len()),
+            None => 0
+        };
+
+        Ok(Self {
             destination_mac: EthernetAddress::new(buf[0], buf[1], buf[2], buf[3], buf[4], buf[5]),
             source_mac: EthernetAddress::new(buf[6], buf[7], buf[8], buf[9], buf[10], buf[11]),
             _type,
             data,
+            trailer: payload[consumed..].to_vec(),
             length: buf.len()
         })
     }
@@ -106,13 +140,12 @@ impl Layer for EthernetFrame {
         buf.splice(6..12, self.source_mac.to_bytes());
         buf.splice(12..14, self._type.get_code().to_be_bytes());
 
-        match &self.data {
-            Some(data) => {
-                buf.extend(data.to_bytes());
-            }
-            None => {}
+        if let Some(data) = &self.data {
+            buf.extend(data.to_bytes());
         }
 
+        buf.extend_from_slice(&self.trailer);
+
         buf
     }
 
@@ -123,10 +156,10 @@ impl Layer for EthernetFrame {
     fn compute_length(&mut self) -> usize {
         self.length = match &self.data {
             Some(layer) => {
-                layer.len() + ETHERNET_FRAME_LEN
+                layer.len() + ETHERNET_FRAME_LEN + self.trailer.len()
             }
             None => {
-                ETHERNET_FRAME_LEN
+                ETHERNET_FRAME_LEN + self.trailer.len()
             }
         };
 
@@ -144,4 +177,12 @@ impl Layer for EthernetFrame {
     fn dyn_clone(&self) -> Box<dyn Layer> {
         Box::new(self.clone())
     }
+
+    fn get_next_layer(&self) -> Option<&Box<dyn Layer>> {
+        self.data.as_ref()
+    }
+
+    fn get_next_layer_mut(&mut self) -> Option<&mut Box<dyn Layer>> {
+        self.data.as_mut()
+    }
 }
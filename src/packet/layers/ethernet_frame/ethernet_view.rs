@@ -0,0 +1,54 @@
+use crate::packet::inter::decode_error::DecodeError;
+use crate::packet::layers::ethernet_frame::ethernet_frame::EthernetFrame;
+use crate::packet::layers::ethernet_frame::inter::ethernet_address::EthernetAddress;
+use crate::packet::layers::ethernet_frame::inter::types::Types;
+use crate::packet::layers::inter::layer::Layer;
+
+const ETHERNET_FRAME_LEN: usize = 14;
+
+#[derive(Clone, Copy, Debug)]
+pub struct EthernetView<'a> {
+    buf: &'a [u8]
+}
+
+impl<'a> EthernetView<'a> {
+
+    pub fn new(buf: &'a [u8]) -> Result<Self, DecodeError> {
+        if buf.len() < ETHERNET_FRAME_LEN {
+            return Err(DecodeError::Truncated {
+                layer: "Ethernet",
+                offset: 0,
+                needed: ETHERNET_FRAME_LEN,
+                available: buf.len()
+            });
+        }
+
+        Ok(Self {
+            buf
+        })
+    }
+
+    pub fn get_destination_mac(&self) -> EthernetAddress {
+        EthernetAddress::new(self.buf[0], self.buf[1], self.buf[2], self.buf[3], self.buf[4], self.buf[5])
+    }
+
+    pub fn get_source_mac(&self) -> EthernetAddress {
+        EthernetAddress::new(self.buf[6], self.buf[7], self.buf[8], self.buf[9], self.buf[10], self.buf[11])
+    }
+
+    pub fn get_type(&self) -> Types {
+        Types::from_code(u16::from_be_bytes([self.buf[12], self.buf[13]]))
+    }
+
+    pub fn get_payload(&self) -> &'a [u8] {
+        &self.buf[ETHERNET_FRAME_LEN..]
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.buf
+    }
+
+    pub fn to_layer(&self) -> Result<EthernetFrame, DecodeError> {
+        EthernetFrame::from_bytes(self.buf)
+    }
+}
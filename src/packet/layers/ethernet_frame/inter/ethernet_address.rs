@@ -15,6 +15,7 @@ impl EthernetAddress {
         self.address
     }
 
+    #[allow(clippy::inherent_to_string)]
     pub fn to_string(&self) -> String {
         self.address.iter()
             .map(|byte| format!("{:02X}", byte))
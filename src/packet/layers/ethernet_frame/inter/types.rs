@@ -3,19 +3,22 @@ pub enum Types {
     IPv4,
     Arp,
     IPv6,
-    Broadcast
+    Broadcast,
+    Vlan,
+    QinQ,
+    Unknown(u16)
 }
 
 impl Types {
 
-    pub fn from_code(code: u16) -> Result<Self, String> {
-        for c in [Self::IPv4, Self::Arp, Self::IPv6, Self::Broadcast] {
+    pub fn from_code(code: u16) -> Self {
+        for c in [Self::IPv4, Self::Arp, Self::IPv6, Self::Broadcast, Self::Vlan, Self::QinQ] {
             if c.get_code() == code {
-                return Ok(c);
+                return c;
             }
         }
 
-        Err(format!("Couldn't find for code: {}", code))
+        Self::Unknown(code)
     }
 
     pub fn get_code(&self) -> u16 {
@@ -23,16 +26,23 @@ impl Types {
             Self::IPv4 => 2048,
             Self::Arp => 2054,
             Self::IPv6 => 34525,
-            Self::Broadcast => 34969
+            Self::Broadcast => 34969,
+            Self::Vlan => 33024,
+            Self::QinQ => 34984,
+            Self::Unknown(code) => *code
         }
     }
 
+    #[allow(clippy::inherent_to_string)]
     pub fn to_string(&self) -> String {
         match self {
             Self::IPv4 => "IPv4",
             Self::Arp => "ARP",
             Self::IPv6 => "IPv6",
-            Self::Broadcast => "Broadcast"
+            Self::Broadcast => "Broadcast",
+            Self::Vlan => "802.1Q",
+            Self::QinQ => "802.1ad",
+            Self::Unknown(_) => "Unknown"
         }.to_string()
     }
 }
@@ -1,4 +1,6 @@
 use std::any::Any;
+use crate::packet::inter::decode_error::DecodeError;
+use crate::packet::layers::ethernet_frame::ip::inter::utils::calculate_checksum;
 use crate::packet::layers::inter::layer::Layer;
 
 const ICMP_PAYLOAD_SIZE: usize = 8;
@@ -24,6 +26,24 @@ impl IcmpLayer {
         self.code
     }
 
+    pub(crate) fn calculate_checksum(&self) -> u16 {
+        let mut buf = self.to_bytes();
+        buf[2] = 0;
+        buf[3] = 0;
+
+        calculate_checksum(&buf)
+    }
+
+    pub fn compute_checksum(&mut self) -> u16 {
+        let checksum = self.calculate_checksum();
+        self.checksum = checksum;
+        checksum
+    }
+
+    pub fn validate_checksum(&self) -> bool {
+        self.checksum == self.calculate_checksum()
+    }
+
     pub fn get_checksum(&self) -> u16 {
         self.checksum
     }
@@ -39,12 +59,17 @@ impl IcmpLayer {
 
 impl Layer for IcmpLayer {
     
-    fn from_bytes(buf: &[u8]) -> Option<Self> {
+    fn from_bytes(buf: &[u8]) -> Result<Self, DecodeError> {
         if buf.len() < ICMP_PAYLOAD_SIZE {
-            return None;
+            return Err(DecodeError::Truncated {
+                layer: "ICMP",
+                offset: 0,
+                needed: ICMP_PAYLOAD_SIZE,
+                available: buf.len()
+            });
         }
 
-        Some(Self {
+        Ok(Self {
             _type: buf[0],
             code: buf[1],
             checksum: u16::from_be_bytes([buf[2], buf[3]]),
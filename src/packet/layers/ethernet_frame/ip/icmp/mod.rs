@@ -1 +1,2 @@
 pub mod icmp_layer;
+pub mod icmp_view;
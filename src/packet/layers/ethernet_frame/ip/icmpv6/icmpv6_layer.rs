@@ -1,4 +1,8 @@
 use std::any::Any;
+use std::net::{IpAddr, Ipv6Addr};
+use crate::packet::inter::decode_error::DecodeError;
+use crate::packet::layers::ethernet_frame::ip::inter::protocols::Protocols;
+use crate::packet::layers::ethernet_frame::ip::inter::utils::{calculate_checksum, pseudo_header};
 use crate::packet::layers::inter::layer::Layer;
 
 const ICMPV6_HEADER_SIZE: usize = 8;
@@ -24,6 +28,26 @@ impl Icmpv6Layer {
         self.code
     }
 
+    pub(crate) fn calculate_checksum(&self, source_address: Ipv6Addr, destination_address: Ipv6Addr) -> u16 {
+        let mut buf = pseudo_header(IpAddr::V6(source_address), IpAddr::V6(destination_address), Protocols::Icmpv6, self.length);
+        let mut header = self.to_bytes();
+        header[2] = 0;
+        header[3] = 0;
+        buf.extend(header);
+
+        calculate_checksum(&buf)
+    }
+
+    pub fn compute_checksum(&mut self, source_address: Ipv6Addr, destination_address: Ipv6Addr) -> u16 {
+        let checksum = self.calculate_checksum(source_address, destination_address);
+        self.checksum = checksum;
+        checksum
+    }
+
+    pub fn validate_checksum(&self, source_address: Ipv6Addr, destination_address: Ipv6Addr) -> bool {
+        self.checksum == self.calculate_checksum(source_address, destination_address)
+    }
+
     pub fn get_checksum(&self) -> u16 {
         self.checksum
     }
@@ -39,12 +63,17 @@ impl Icmpv6Layer {
 
 impl Layer for Icmpv6Layer {
 
-    fn from_bytes(buf: &[u8]) -> Option<Self> {
+    fn from_bytes(buf: &[u8]) -> Result<Self, DecodeError> {
         if buf.len() < ICMPV6_HEADER_SIZE {
-            return None;
+            return Err(DecodeError::Truncated {
+                layer: "ICMPv6",
+                offset: 0,
+                needed: ICMPV6_HEADER_SIZE,
+                available: buf.len()
+            });
         }
 
-        Some(Self {
+        Ok(Self {
             _type: buf[0],
             code: buf[1],
             checksum: u16::from_be_bytes([buf[2], buf[3]]),
@@ -0,0 +1,60 @@
+use crate::packet::inter::decode_error::DecodeError;
+use crate::packet::layers::ethernet_frame::ip::icmpv6::icmpv6_layer::Icmpv6Layer;
+use crate::packet::layers::inter::layer::Layer;
+
+const ICMPV6_HEADER_SIZE: usize = 8;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Icmpv6View<'a> {
+    buf: &'a [u8]
+}
+
+impl<'a> Icmpv6View<'a> {
+
+    pub fn new(buf: &'a [u8]) -> Result<Self, DecodeError> {
+        if buf.len() < ICMPV6_HEADER_SIZE {
+            return Err(DecodeError::Truncated {
+                layer: "ICMPv6",
+                offset: 0,
+                needed: ICMPV6_HEADER_SIZE,
+                available: buf.len()
+            });
+        }
+
+        Ok(Self {
+            buf
+        })
+    }
+
+    pub fn get_type(&self) -> u8 {
+        self.buf[0]
+    }
+
+    pub fn get_code(&self) -> u8 {
+        self.buf[1]
+    }
+
+    pub fn get_checksum(&self) -> u16 {
+        u16::from_be_bytes([self.buf[2], self.buf[3]])
+    }
+
+    pub fn get_identifier(&self) -> u16 {
+        u16::from_be_bytes([self.buf[4], self.buf[5]])
+    }
+
+    pub fn get_sequence_number(&self) -> u16 {
+        u16::from_be_bytes([self.buf[6], self.buf[7]])
+    }
+
+    pub fn get_data(&self) -> &'a [u8] {
+        &self.buf[ICMPV6_HEADER_SIZE..]
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.buf
+    }
+
+    pub fn to_layer(&self) -> Result<Icmpv6Layer, DecodeError> {
+        Icmpv6Layer::from_bytes(self.buf)
+    }
+}
@@ -1 +1,2 @@
 pub mod icmpv6_layer;
+pub mod icmpv6_view;
@@ -9,19 +9,20 @@ pub enum Protocols {
     Gre,
     Icmpv6,
     Ospf,
-    Sps
+    Sps,
+    Unknown(u8)
 }
 
 impl Protocols {
 
-    pub fn from_code(code: u8) -> Result<Self, String> {
+    pub fn from_code(code: u8) -> Self {
         for c in [Self::HopByHop, Self::Icmp, Self::Igmp, Self::Tcp, Self::Udp, Self::Ipv6, Self::Icmpv6, Self::Gre, Self::Ospf, Self::Sps] {
             if c.get_code() == code {
-                return Ok(c);
+                return c;
             }
         }
 
-        Err(format!("Couldn't find for code: {}", code))
+        Self::Unknown(code)
     }
 
     pub fn get_code(&self) -> u8 {
@@ -35,10 +36,12 @@ impl Protocols {
             Self::Gre => 47,
             Self::Icmpv6 => 58,
             Self::Ospf => 89,
-            Self::Sps => 128
+            Self::Sps => 128,
+            Self::Unknown(code) => *code
         }
     }
 
+    #[allow(clippy::inherent_to_string)]
     pub fn to_string(&self) -> String {
         match self {
             Self::HopByHop => "HOP_BY_HOP",
@@ -50,7 +53,8 @@ impl Protocols {
             Self::Gre => "GRE",
             Self::Icmpv6 => "ICMPv6",
             Self::Ospf => "OSPF",
-            Self::Sps => "SPS"
+            Self::Sps => "SPS",
+            Self::Unknown(_) => "Unknown"
         }.to_string()
     }
 }
@@ -1,3 +1,6 @@
+use std::net::IpAddr;
+use crate::packet::layers::ethernet_frame::ip::inter::protocols::Protocols;
+
 pub fn calculate_checksum(buf: &[u8]) -> u16 {
     let mut sum: u32 = 0;
 
@@ -16,3 +19,33 @@ pub fn calculate_checksum(buf: &[u8]) -> u16 {
 
     !(sum as u16)
 }
+
+//PREFIXED TO TCP, UDP AND ICMPV6 WHEN CALCULATING THEIR CHECKSUMS
+pub fn pseudo_header(source_address: IpAddr, destination_address: IpAddr, protocol: Protocols, length: usize) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(40);
+
+    match (source_address, destination_address) {
+        (IpAddr::V4(source), IpAddr::V4(destination)) => {
+            buf.extend_from_slice(&source.octets());
+            buf.extend_from_slice(&destination.octets());
+            buf.push(0);
+            buf.push(protocol.get_code());
+            buf.extend_from_slice(&(length as u16).to_be_bytes());
+        }
+        _ => {
+            buf.extend_from_slice(&to_ipv6_octets(source_address));
+            buf.extend_from_slice(&to_ipv6_octets(destination_address));
+            buf.extend_from_slice(&(length as u32).to_be_bytes());
+            buf.extend_from_slice(&[0, 0, 0, protocol.get_code()]);
+        }
+    }
+
+    buf
+}
+
+fn to_ipv6_octets(address: IpAddr) -> [u8; 16] {
+    match address {
+        IpAddr::V4(address) => address.to_ipv6_mapped().octets(),
+        IpAddr::V6(address) => address.octets()
+    }
+}
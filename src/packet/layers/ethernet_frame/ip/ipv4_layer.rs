@@ -1,21 +1,16 @@
 use std::any::Any;
 use std::net::Ipv4Addr;
+use crate::packet::inter::decode_error::DecodeError;
 use crate::packet::layers::ethernet_frame::ip::icmp::icmp_layer::IcmpLayer;
 use crate::packet::layers::ethernet_frame::ip::inter::protocols::Protocols;
 use crate::packet::layers::ethernet_frame::ip::inter::utils::calculate_checksum;
 use crate::packet::layers::ethernet_frame::ip::tcp::tcp_layer::TcpLayer;
 use crate::packet::layers::ethernet_frame::ip::udp::udp_layer::UdpLayer;
 use crate::packet::layers::inter::layer::Layer;
+use crate::packet::layers::raw::raw_layer::RawLayer;
 
 const IPV4_HEADER_SIZE: usize = 20;
 
-/*
-let ihl = (packet[0] & 0x0F) as usize * 4; // Internet Header Length (IHL)
-if ihl < IPV4_HEADER_SIZE || ihl > packet.len() {
-    return Err(io::Error::new(io::ErrorKind::Other, "Packet has invalid IHL")); // Too short to be an IPv4 packet
-}
-*/
-
 #[derive(Clone, Debug)]
 pub struct Ipv4Layer {
     version: u8,
@@ -30,6 +25,7 @@ pub struct Ipv4Layer {
     checksum: u16,
     source_address: Ipv4Addr,
     destination_address: Ipv4Addr,
+    options: Vec<u8>,
     data: Option<Box<dyn Layer>>
 }
 
@@ -49,6 +45,7 @@ impl Ipv4Layer {
             checksum: 0,
             source_address,
             destination_address,
+            options: Vec::new(),
             data: None
         }
     }
@@ -121,7 +118,7 @@ impl Ipv4Layer {
         self.protocol
     }
 
-    fn calculate_checksum(&self) -> u16 {
+    pub(crate) fn calculate_checksum(&self) -> u16 {
         let mut buf = vec![0; IPV4_HEADER_SIZE];
 
         buf[0] = (self.version << 4) | (self.ihl & 0x0F);
@@ -134,6 +131,7 @@ impl Ipv4Layer {
         buf[9] = self.protocol.get_code();
         buf.splice(12..16, self.source_address.octets());
         buf.splice(16..20, self.destination_address.octets());
+        buf.extend_from_slice(&self.options);
 
         calculate_checksum(&buf)
     }
@@ -168,11 +166,22 @@ impl Ipv4Layer {
         self.destination_address
     }
 
+    //OPTIONS MUST BE PADDED TO A MULTIPLE OF 4 BYTES
+    pub fn set_options(&mut self, options: Vec<u8>) {
+        self.ihl = ((IPV4_HEADER_SIZE + options.len()) / 4) as u8;
+        self.options = options;
+    }
+
+    pub fn get_options(&self) -> &Vec<u8> {
+        &self.options
+    }
+
     pub fn set_data(&mut self, data: Box<dyn Layer>) {
-        self.total_length = (data.len() + IPV4_HEADER_SIZE) as u16;
+        self.total_length = (data.len() + self.ihl as usize * 4) as u16;
         self.data = Some(data);
     }
 
+    #[allow(clippy::borrowed_box)]
     pub fn get_data(&self) -> Option<&Box<dyn Layer>> {
         self.data.as_ref()
     }
@@ -184,63 +193,90 @@ impl Ipv4Layer {
 
 impl Layer for Ipv4Layer {
 
-    fn from_bytes(buf: &[u8]) -> Option<Self> {
+    fn from_bytes(buf: &[u8]) -> Result<Self, DecodeError> {
         if buf.len() < IPV4_HEADER_SIZE {
-            return None;
+            return Err(DecodeError::Truncated {
+                layer: "IPv4",
+                offset: 0,
+                needed: IPV4_HEADER_SIZE,
+                available: buf.len()
+            });
         }
 
         let version_ihl = buf[0];
         let version = version_ihl >> 4;
         let ihl = version_ihl & 0x0F;
+        let header_len = ihl as usize * 4;
+
+        if header_len < IPV4_HEADER_SIZE {
+            return Err(DecodeError::BadLength {
+                layer: "IPv4",
+                offset: 0,
+                length: header_len
+            });
+        }
+
+        if header_len > buf.len() {
+            return Err(DecodeError::Truncated {
+                layer: "IPv4",
+                offset: 0,
+                needed: header_len,
+                available: buf.len()
+            });
+        }
+
+        let total_length = u16::from_be_bytes([buf[2], buf[3]]);
+
+        if (total_length as usize) < header_len {
+            return Err(DecodeError::BadLength {
+                layer: "IPv4",
+                offset: 2,
+                length: total_length as usize
+            });
+        }
 
-        let protocol = Protocols::from_code(buf[9]).unwrap();
+        let protocol = Protocols::from_code(buf[9]);
+        let fragment_offset = u16::from_be_bytes([buf[6] & 0x1F, buf[7]]);
 
-        let data = match protocol {
-            Protocols::HopByHop => {
+        //ANYTHING PAST TOTAL LENGTH IS LINK LAYER PADDING, A SHORTER BUFFER MEANS THE CAPTURE WAS CUT
+        let payload = &buf[header_len..buf.len().min(total_length as usize)];
+
+        let data: Option<Box<dyn Layer>> = match protocol {
+            _ if payload.is_empty() => {
                 None
             }
-            Protocols::Icmp => {
-                Some(IcmpLayer::from_bytes(&buf[IPV4_HEADER_SIZE..])?.dyn_clone())
+            //ONLY THE FIRST FRAGMENT CARRIES THE NEXT HEADER
+            _ if fragment_offset != 0 => {
+                Some(Box::new(RawLayer::new(protocol.get_code() as u16, payload.to_vec())))
             }
-            Protocols::Igmp => {
-                None
+            Protocols::Icmp => {
+                Some(Box::new(IcmpLayer::from_bytes(payload).map_err(|e| e.shift(header_len))?))
             }
             Protocols::Tcp => {
-                Some(TcpLayer::from_bytes(&buf[IPV4_HEADER_SIZE..])?.dyn_clone())
+                Some(Box::new(TcpLayer::from_bytes(payload).map_err(|e| e.shift(header_len))?))
             }
             Protocols::Udp => {
-                Some(UdpLayer::from_bytes(&buf[IPV4_HEADER_SIZE..])?.dyn_clone())
+                Some(Box::new(UdpLayer::from_bytes(payload).map_err(|e| e.shift(header_len))?))
             }
-            Protocols::Ipv6 => {
-                None
-            }
-            Protocols::Gre => {
-                None
-            }
-            Protocols::Icmpv6 => {
-                None
-            }
-            Protocols::Ospf => {
-                None
-            }
-            Protocols::Sps => {
-                None
+            _ => {
+                Some(Box::new(RawLayer::new(protocol.get_code() as u16, payload.to_vec())))
             }
         };
 
-        Some(Self {
+        Ok(Self {
             version,
             ihl,
             tos: buf[1],
-            total_length: u16::from_be_bytes([buf[2], buf[3]]),
+            total_length,
             identification: u16::from_be_bytes([buf[4], buf[5]]),
             flags: buf[6] >> 5,
-            fragment_offset: u16::from_be_bytes([buf[6] & 0x1F, buf[7]]),
+            fragment_offset,
             ttl: buf[8],
             protocol,
             checksum: u16::from_be_bytes([buf[10], buf[11]]),
             source_address: Ipv4Addr::new(buf[12], buf[13], buf[14], buf[15]),
             destination_address: Ipv4Addr::new(buf[16], buf[17], buf[18], buf[19]),
+            options: buf[IPV4_HEADER_SIZE..header_len].to_vec(),
             data
         })
     }
@@ -259,12 +295,10 @@ impl Layer for Ipv4Layer {
         buf.splice(10..12, self.checksum.to_be_bytes());
         buf.splice(12..16, self.source_address.octets());
         buf.splice(16..20, self.destination_address.octets());
+        buf.extend_from_slice(&self.options);
 
-        match &self.data {
-            Some(data) => {
-                buf.extend(data.to_bytes());
-            }
-            None => {}
+        if let Some(data) = &self.data {
+            buf.extend(data.to_bytes());
         }
 
         buf
@@ -277,10 +311,10 @@ impl Layer for Ipv4Layer {
     fn compute_length(&mut self) -> usize {
         self.total_length = match &self.data {
             Some(layer) => {
-                layer.len() + IPV4_HEADER_SIZE
+                layer.len() + IPV4_HEADER_SIZE + self.options.len()
             }
             None => {
-                IPV4_HEADER_SIZE
+                IPV4_HEADER_SIZE + self.options.len()
             }
         } as u16;
 
@@ -298,4 +332,12 @@ impl Layer for Ipv4Layer {
     fn dyn_clone(&self) -> Box<dyn Layer> {
         Box::new(self.clone())
     }
+
+    fn get_next_layer(&self) -> Option<&Box<dyn Layer>> {
+        self.data.as_ref()
+    }
+
+    fn get_next_layer_mut(&mut self) -> Option<&mut Box<dyn Layer>> {
+        self.data.as_mut()
+    }
 }
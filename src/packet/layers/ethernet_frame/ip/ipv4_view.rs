@@ -0,0 +1,130 @@
+use std::net::Ipv4Addr;
+use crate::packet::inter::decode_error::DecodeError;
+use crate::packet::layers::ethernet_frame::ip::inter::protocols::Protocols;
+use crate::packet::layers::ethernet_frame::ip::inter::utils::calculate_checksum;
+use crate::packet::layers::ethernet_frame::ip::ipv4_layer::Ipv4Layer;
+use crate::packet::layers::inter::layer::Layer;
+
+const IPV4_HEADER_SIZE: usize = 20;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Ipv4View<'a> {
+    buf: &'a [u8]
+}
+
+impl<'a> Ipv4View<'a> {
+
+    pub fn new(buf: &'a [u8]) -> Result<Self, DecodeError> {
+        if buf.len() < IPV4_HEADER_SIZE {
+            return Err(DecodeError::Truncated {
+                layer: "IPv4",
+                offset: 0,
+                needed: IPV4_HEADER_SIZE,
+                available: buf.len()
+            });
+        }
+
+        let header_len = (buf[0] & 0x0F) as usize * 4;
+
+        if header_len < IPV4_HEADER_SIZE {
+            return Err(DecodeError::BadLength {
+                layer: "IPv4",
+                offset: 0,
+                length: header_len
+            });
+        }
+
+        if header_len > buf.len() {
+            return Err(DecodeError::Truncated {
+                layer: "IPv4",
+                offset: 0,
+                needed: header_len,
+                available: buf.len()
+            });
+        }
+
+        let total_length = u16::from_be_bytes([buf[2], buf[3]]) as usize;
+
+        if total_length < header_len {
+            return Err(DecodeError::BadLength {
+                layer: "IPv4",
+                offset: 2,
+                length: total_length
+            });
+        }
+
+        Ok(Self {
+            buf
+        })
+    }
+
+    pub fn get_version(&self) -> u8 {
+        self.buf[0] >> 4
+    }
+
+    pub fn get_ihl(&self) -> u8 {
+        self.buf[0] & 0x0F
+    }
+
+    pub fn get_tos(&self) -> u8 {
+        self.buf[1]
+    }
+
+    pub fn get_total_length(&self) -> u16 {
+        u16::from_be_bytes([self.buf[2], self.buf[3]])
+    }
+
+    pub fn get_identification(&self) -> u16 {
+        u16::from_be_bytes([self.buf[4], self.buf[5]])
+    }
+
+    pub fn get_flags(&self) -> u8 {
+        self.buf[6] >> 5
+    }
+
+    pub fn get_fragment_offset(&self) -> u16 {
+        u16::from_be_bytes([self.buf[6] & 0x1F, self.buf[7]])
+    }
+
+    pub fn get_ttl(&self) -> u8 {
+        self.buf[8]
+    }
+
+    pub fn get_protocol(&self) -> Protocols {
+        Protocols::from_code(self.buf[9])
+    }
+
+    pub fn get_checksum(&self) -> u16 {
+        u16::from_be_bytes([self.buf[10], self.buf[11]])
+    }
+
+    //A VALID HEADER SUMS TO ZERO WITH ITS CHECKSUM IN PLACE
+    pub fn validate_checksum(&self) -> bool {
+        calculate_checksum(&self.buf[..self.get_ihl() as usize * 4]) == 0
+    }
+
+    pub fn get_source_address(&self) -> Ipv4Addr {
+        Ipv4Addr::new(self.buf[12], self.buf[13], self.buf[14], self.buf[15])
+    }
+
+    pub fn get_destination_address(&self) -> Ipv4Addr {
+        Ipv4Addr::new(self.buf[16], self.buf[17], self.buf[18], self.buf[19])
+    }
+
+    pub fn get_options(&self) -> &'a [u8] {
+        &self.buf[IPV4_HEADER_SIZE..self.get_ihl() as usize * 4]
+    }
+
+    //BOUNDED BY TOTAL LENGTH SO LINK LAYER PADDING ISN'T INCLUDED
+    pub fn get_payload(&self) -> &'a [u8] {
+        &self.buf[self.get_ihl() as usize * 4..self.buf.len().min(self.get_total_length() as usize)]
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.buf
+    }
+
+    pub fn to_layer(&self) -> Result<Ipv4Layer, DecodeError> {
+        Ipv4Layer::from_bytes(self.buf)
+    }
+}
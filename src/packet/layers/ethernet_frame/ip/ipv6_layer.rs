@@ -1,10 +1,12 @@
 use std::any::Any;
 use std::net::Ipv6Addr;
+use crate::packet::inter::decode_error::DecodeError;
 use crate::packet::layers::ethernet_frame::ip::icmpv6::icmpv6_layer::Icmpv6Layer;
 use crate::packet::layers::ethernet_frame::ip::inter::protocols::Protocols;
 use crate::packet::layers::ethernet_frame::ip::tcp::tcp_layer::TcpLayer;
 use crate::packet::layers::ethernet_frame::ip::udp::udp_layer::UdpLayer;
 use crate::packet::layers::inter::layer::Layer;
+use crate::packet::layers::raw::raw_layer::RawLayer;
 
 const IPV6_HEADER_SIZE: usize = 40;
 
@@ -55,6 +57,7 @@ impl Ipv6Layer {
         self.destination_address
     }
 
+    #[allow(clippy::borrowed_box)]
     pub fn get_data(&self) -> Option<&Box<dyn Layer>> {
         self.data.as_ref()
     }
@@ -66,51 +69,44 @@ impl Ipv6Layer {
 
 impl Layer for Ipv6Layer {
 
-    fn from_bytes(buf: &[u8]) -> Option<Self> {
+    fn from_bytes(buf: &[u8]) -> Result<Self, DecodeError> {
         if buf.len() < IPV6_HEADER_SIZE {
-            return None;
+            return Err(DecodeError::Truncated {
+                layer: "IPv6",
+                offset: 0,
+                needed: IPV6_HEADER_SIZE,
+                available: buf.len()
+            });
         }
 
-        let next_header = Protocols::from_code(buf[6]).unwrap();
+        let next_header = Protocols::from_code(buf[6]);
+        let payload_length = u16::from_be_bytes([buf[4], buf[5]]);
 
-        let data = match next_header {
-            Protocols::HopByHop => {
-                None
-            }
-            Protocols::Icmp => {
-                None
-            }
-            Protocols::Igmp => {
+        let payload = &buf[IPV6_HEADER_SIZE..buf.len().min(IPV6_HEADER_SIZE + payload_length as usize)];
+
+        let data: Option<Box<dyn Layer>> = match next_header {
+            _ if payload.is_empty() => {
                 None
             }
             Protocols::Tcp => {
-                Some(TcpLayer::from_bytes(&buf[IPV6_HEADER_SIZE..])?.dyn_clone())
+                Some(Box::new(TcpLayer::from_bytes(payload).map_err(|e| e.shift(IPV6_HEADER_SIZE))?))
             }
             Protocols::Udp => {
-                Some(UdpLayer::from_bytes(&buf[IPV6_HEADER_SIZE..])?.dyn_clone())
-            }
-            Protocols::Ipv6 => {
-                None
-            }
-            Protocols::Gre => {
-                None
+                Some(Box::new(UdpLayer::from_bytes(payload).map_err(|e| e.shift(IPV6_HEADER_SIZE))?))
             }
             Protocols::Icmpv6 => {
-                Some(Icmpv6Layer::from_bytes(&buf[IPV6_HEADER_SIZE..])?.dyn_clone())
+                Some(Box::new(Icmpv6Layer::from_bytes(payload).map_err(|e| e.shift(IPV6_HEADER_SIZE))?))
             }
-            Protocols::Ospf => {
-                None
-            }
-            Protocols::Sps => {
-                None
+            _ => {
+                Some(Box::new(RawLayer::new(next_header.get_code() as u16, payload.to_vec())))
             }
         };
 
-        Some(Self {
+        Ok(Self {
             version: (buf[0] >> 4) & 0x0F,
             traffic_class: ((buf[0] & 0x0F) << 4) | (buf[1] >> 4),
             flow_label: ((buf[1] as u32 & 0x0F) << 16) | ((buf[2] as u32) << 8) | (buf[3] as u32),
-            payload_length: u16::from_be_bytes([buf[4], buf[5]]),
+            payload_length,
             next_header,
             hop_limit: buf[7],
             source_address: Ipv6Addr::from(<[u8; 16]>::try_from(&buf[8..24]).unwrap()),
@@ -132,11 +128,8 @@ impl Layer for Ipv6Layer {
         buf.splice(8..24, self.source_address.octets());
         buf.splice(24..40, self.destination_address.octets());
 
-        match &self.data {
-            Some(data) => {
-                buf.extend(data.to_bytes());
-            }
-            None => {}
+        if let Some(data) = &self.data {
+            buf.extend(data.to_bytes());
         }
 
         buf
@@ -171,4 +164,12 @@ impl Layer for Ipv6Layer {
     fn dyn_clone(&self) -> Box<dyn Layer> {
         Box::new(self.clone())
     }
+
+    fn get_next_layer(&self) -> Option<&Box<dyn Layer>> {
+        self.data.as_ref()
+    }
+
+    fn get_next_layer_mut(&mut self) -> Option<&mut Box<dyn Layer>> {
+        self.data.as_mut()
+    }
 }
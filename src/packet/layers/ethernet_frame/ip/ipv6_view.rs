@@ -0,0 +1,74 @@
+use std::net::Ipv6Addr;
+use crate::packet::inter::decode_error::DecodeError;
+use crate::packet::layers::ethernet_frame::ip::inter::protocols::Protocols;
+use crate::packet::layers::ethernet_frame::ip::ipv6_layer::Ipv6Layer;
+use crate::packet::layers::inter::layer::Layer;
+
+const IPV6_HEADER_SIZE: usize = 40;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Ipv6View<'a> {
+    buf: &'a [u8]
+}
+
+impl<'a> Ipv6View<'a> {
+
+    pub fn new(buf: &'a [u8]) -> Result<Self, DecodeError> {
+        if buf.len() < IPV6_HEADER_SIZE {
+            return Err(DecodeError::Truncated {
+                layer: "IPv6",
+                offset: 0,
+                needed: IPV6_HEADER_SIZE,
+                available: buf.len()
+            });
+        }
+
+        Ok(Self {
+            buf
+        })
+    }
+
+    pub fn get_version(&self) -> u8 {
+        self.buf[0] >> 4
+    }
+
+    pub fn get_traffic_class(&self) -> u8 {
+        ((self.buf[0] & 0x0F) << 4) | (self.buf[1] >> 4)
+    }
+
+    pub fn get_flow_label(&self) -> u32 {
+        ((self.buf[1] as u32 & 0x0F) << 16) | ((self.buf[2] as u32) << 8) | (self.buf[3] as u32)
+    }
+
+    pub fn get_payload_length(&self) -> u16 {
+        u16::from_be_bytes([self.buf[4], self.buf[5]])
+    }
+
+    pub fn get_next_header(&self) -> Protocols {
+        Protocols::from_code(self.buf[6])
+    }
+
+    pub fn get_hop_limit(&self) -> u8 {
+        self.buf[7]
+    }
+
+    pub fn get_source_address(&self) -> Ipv6Addr {
+        Ipv6Addr::from(<[u8; 16]>::try_from(&self.buf[8..24]).unwrap())
+    }
+
+    pub fn get_destination_address(&self) -> Ipv6Addr {
+        Ipv6Addr::from(<[u8; 16]>::try_from(&self.buf[24..40]).unwrap())
+    }
+
+    pub fn get_payload(&self) -> &'a [u8] {
+        &self.buf[IPV6_HEADER_SIZE..self.buf.len().min(IPV6_HEADER_SIZE + self.get_payload_length() as usize)]
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.buf
+    }
+
+    pub fn to_layer(&self) -> Result<Ipv6Layer, DecodeError> {
+        Ipv6Layer::from_bytes(self.buf)
+    }
+}
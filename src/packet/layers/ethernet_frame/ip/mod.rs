@@ -5,3 +5,5 @@ pub mod udp;
 pub mod tcp;
 pub mod ipv4_layer;
 pub mod ipv6_layer;
+pub mod ipv4_view;
+pub mod ipv6_view;
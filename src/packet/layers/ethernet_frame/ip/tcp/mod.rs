@@ -1 +1,2 @@
 pub mod tcp_layer;
+pub mod tcp_view;
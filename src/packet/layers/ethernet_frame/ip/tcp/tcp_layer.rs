@@ -1,4 +1,8 @@
 use std::any::Any;
+use std::net::IpAddr;
+use crate::packet::inter::decode_error::DecodeError;
+use crate::packet::layers::ethernet_frame::ip::inter::protocols::Protocols;
+use crate::packet::layers::ethernet_frame::ip::inter::utils::{calculate_checksum, pseudo_header};
 use crate::packet::layers::inter::layer::Layer;
 
 const TCP_HEADER_SIZE: usize = 20;
@@ -14,24 +18,58 @@ pub struct TcpLayer {
     window_size: u16,
     checksum: u16,
     urgent_pointer: u16,
+    options: Vec<u8>,
     payload: Option<Vec<u8>>,
     length: usize
 }
 
 impl TcpLayer {
 
+    pub fn new(source_port: u16, destination_port: u16) -> Self {
+        Self {
+            source_port,
+            destination_port,
+            sequence_number: 0,
+            acknowledgment_number: 0,
+            data_offset: TCP_HEADER_SIZE as u8,
+            flags: 0,
+            window_size: 0,
+            checksum: 0,
+            urgent_pointer: 0,
+            options: Vec::new(),
+            payload: None,
+            length: TCP_HEADER_SIZE
+        }
+    }
+
+    pub fn set_source_port(&mut self, source_port: u16) {
+        self.source_port = source_port;
+    }
+
     pub fn get_source_port(&self) -> u16 {
         self.source_port
     }
 
+    pub fn set_destination_port(&mut self, destination_port: u16) {
+        self.destination_port = destination_port;
+    }
+
     pub fn get_destination_port(&self) -> u16 {
         self.destination_port
     }
 
+    pub fn set_sequence_number(&mut self, sequence_number: u32) {
+        self.sequence_number = sequence_number;
+    }
+
     pub fn get_sequence_number(&self) -> u32 {
         self.sequence_number
     }
 
+    pub fn set_acknowledgment_number(&mut self, acknowledgment_number: u32) {
+        self.acknowledgment_number = acknowledgment_number;
+    }
+
     pub fn get_acknowledgment_number(&self) -> u32 {
         self.acknowledgment_number
     }
@@ -40,22 +78,73 @@ impl TcpLayer {
         self.data_offset
     }
 
+    pub fn set_flags(&mut self, flags: u16) {
+        self.flags = flags;
+    }
+
     pub fn get_flags(&self) -> u16 {
         self.flags
     }
 
+    pub fn set_window_size(&mut self, window_size: u16) {
+        self.window_size = window_size;
+    }
+
     pub fn get_window_size(&self) -> u16 {
         self.window_size
     }
 
+    pub(crate) fn calculate_checksum(&self, source_address: IpAddr, destination_address: IpAddr) -> u16 {
+        let mut buf = pseudo_header(source_address, destination_address, Protocols::Tcp, self.length);
+        let mut header = self.to_bytes();
+        header[16] = 0;
+        header[17] = 0;
+        buf.extend(header);
+
+        calculate_checksum(&buf)
+    }
+
+    pub fn compute_checksum(&mut self, source_address: IpAddr, destination_address: IpAddr) -> u16 {
+        let checksum = self.calculate_checksum(source_address, destination_address);
+        self.checksum = checksum;
+        checksum
+    }
+
+    pub fn validate_checksum(&self, source_address: IpAddr, destination_address: IpAddr) -> bool {
+        self.checksum == self.calculate_checksum(source_address, destination_address)
+    }
+
     pub fn get_checksum(&self) -> u16 {
         self.checksum
     }
 
+    pub fn set_urgent_pointer(&mut self, urgent_pointer: u16) {
+        self.urgent_pointer = urgent_pointer;
+    }
+
     pub fn get_urgent_pointer(&self) -> u16 {
         self.urgent_pointer
     }
 
+    //OPTIONS MUST BE PADDED TO 4 BYTES
+    pub fn set_options(&mut self, options: Vec<u8>) {
+        self.data_offset = (TCP_HEADER_SIZE + options.len()) as u8;
+        self.options = options;
+        self.compute_length();
+    }
+
+    pub fn get_options(&self) -> &Vec<u8> {
+        &self.options
+    }
+
+    pub fn set_payload(&mut self, payload: Vec<u8>) {
+        self.payload = match payload.is_empty() {
+            true => None,
+            false => Some(payload)
+        };
+        self.compute_length();
+    }
+
     pub fn get_payload(&self) -> &Option<Vec<u8>> {
         &self.payload
     }
@@ -69,23 +158,51 @@ impl TcpLayer {
 
 impl Layer for TcpLayer {
 
-    fn from_bytes(buf: &[u8]) -> Option<Self> {
+    fn from_bytes(buf: &[u8]) -> Result<Self, DecodeError> {
         if buf.len() < TCP_HEADER_SIZE {
-            return None;
+            return Err(DecodeError::Truncated {
+                layer: "TCP",
+                offset: 0,
+                needed: TCP_HEADER_SIZE,
+                available: buf.len()
+            });
+        }
+
+        let data_offset = (buf[12] >> 4) as usize * 4;
+
+        if data_offset < TCP_HEADER_SIZE {
+            return Err(DecodeError::BadLength {
+                layer: "TCP",
+                offset: 12,
+                length: data_offset
+            });
         }
 
-        Some(Self {
+        if data_offset > buf.len() {
+            return Err(DecodeError::Truncated {
+                layer: "TCP",
+                offset: 0,
+                needed: data_offset,
+                available: buf.len()
+            });
+        }
+
+        Ok(Self {
             source_port: u16::from_be_bytes([buf[0], buf[1]]),
             destination_port: u16::from_be_bytes([buf[2], buf[3]]),
             sequence_number: u32::from_be_bytes([buf[4], buf[5], buf[6], buf[7]]),
             acknowledgment_number: u32::from_be_bytes([buf[8], buf[9], buf[10], buf[11]]),
-            data_offset: (buf[12] >> 4) * 4,
+            data_offset: data_offset as u8,
             flags: u16::from_be_bytes([buf[12] & 0x0F, buf[13]]),
             window_size: u16::from_be_bytes([buf[14], buf[15]]),
             checksum: u16::from_be_bytes([buf[16], buf[17]]),
             urgent_pointer: u16::from_be_bytes([buf[18], buf[19]]),
-            payload: None,
-            length: 0
+            options: buf[TCP_HEADER_SIZE..data_offset].to_vec(),
+            payload: match buf.len() > data_offset {
+                true => Some(buf[data_offset..].to_vec()),
+                false => None
+            },
+            length: buf.len()
         })
     }
 
@@ -102,12 +219,10 @@ impl Layer for TcpLayer {
         buf.splice(14..16, self.window_size.to_be_bytes());
         buf.splice(16..18, self.checksum.to_be_bytes());
         buf.splice(18..20, self.urgent_pointer.to_be_bytes());
+        buf.extend_from_slice(&self.options);
 
-        match &self.payload {
-            Some(payload) => {
-                buf.extend(payload);
-            }
-            None => {}
+        if let Some(payload) = &self.payload {
+            buf.extend(payload);
         }
 
         buf
@@ -118,12 +233,13 @@ impl Layer for TcpLayer {
     }
 
     fn compute_length(&mut self) -> usize {
+        self.data_offset = (TCP_HEADER_SIZE + self.options.len()) as u8;
         self.length = match &self.payload {
             Some(payload) => {
-                payload.len() + TCP_HEADER_SIZE
+                payload.len() + self.data_offset as usize
             }
             None => {
-                TCP_HEADER_SIZE
+                self.data_offset as usize
             }
         };
 
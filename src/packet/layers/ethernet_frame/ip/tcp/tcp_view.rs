@@ -0,0 +1,99 @@
+use crate::packet::inter::decode_error::DecodeError;
+use crate::packet::layers::ethernet_frame::ip::tcp::tcp_layer::TcpLayer;
+use crate::packet::layers::inter::layer::Layer;
+
+const TCP_HEADER_SIZE: usize = 20;
+
+#[derive(Clone, Copy, Debug)]
+pub struct TcpView<'a> {
+    buf: &'a [u8]
+}
+
+impl<'a> TcpView<'a> {
+
+    pub fn new(buf: &'a [u8]) -> Result<Self, DecodeError> {
+        if buf.len() < TCP_HEADER_SIZE {
+            return Err(DecodeError::Truncated {
+                layer: "TCP",
+                offset: 0,
+                needed: TCP_HEADER_SIZE,
+                available: buf.len()
+            });
+        }
+
+        let data_offset = (buf[12] >> 4) as usize * 4;
+
+        if data_offset < TCP_HEADER_SIZE {
+            return Err(DecodeError::BadLength {
+                layer: "TCP",
+                offset: 12,
+                length: data_offset
+            });
+        }
+
+        if data_offset > buf.len() {
+            return Err(DecodeError::Truncated {
+                layer: "TCP",
+                offset: 0,
+                needed: data_offset,
+                available: buf.len()
+            });
+        }
+
+        Ok(Self {
+            buf
+        })
+    }
+
+    pub fn get_source_port(&self) -> u16 {
+        u16::from_be_bytes([self.buf[0], self.buf[1]])
+    }
+
+    pub fn get_destination_port(&self) -> u16 {
+        u16::from_be_bytes([self.buf[2], self.buf[3]])
+    }
+
+    pub fn get_sequence_number(&self) -> u32 {
+        u32::from_be_bytes([self.buf[4], self.buf[5], self.buf[6], self.buf[7]])
+    }
+
+    pub fn get_acknowledgment_number(&self) -> u32 {
+        u32::from_be_bytes([self.buf[8], self.buf[9], self.buf[10], self.buf[11]])
+    }
+
+    pub fn get_data_offset(&self) -> u8 {
+        (self.buf[12] >> 4) * 4
+    }
+
+    pub fn get_flags(&self) -> u16 {
+        u16::from_be_bytes([self.buf[12] & 0x0F, self.buf[13]])
+    }
+
+    pub fn get_window_size(&self) -> u16 {
+        u16::from_be_bytes([self.buf[14], self.buf[15]])
+    }
+
+    pub fn get_checksum(&self) -> u16 {
+        u16::from_be_bytes([self.buf[16], self.buf[17]])
+    }
+
+    pub fn get_urgent_pointer(&self) -> u16 {
+        u16::from_be_bytes([self.buf[18], self.buf[19]])
+    }
+
+    pub fn get_options(&self) -> &'a [u8] {
+        &self.buf[TCP_HEADER_SIZE..self.get_data_offset() as usize]
+    }
+
+    pub fn get_payload(&self) -> &'a [u8] {
+        &self.buf[self.get_data_offset() as usize..]
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.buf
+    }
+
+    pub fn to_layer(&self) -> Result<TcpLayer, DecodeError> {
+        TcpLayer::from_bytes(self.buf)
+    }
+}
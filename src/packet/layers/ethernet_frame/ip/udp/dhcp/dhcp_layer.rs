@@ -1,8 +1,11 @@
 use std::any::Any;
+use crate::packet::inter::decode_error::DecodeError;
 use crate::packet::layers::ethernet_frame::ip::udp::dhcp::inter::dhcp_cookie::DhcpCookie;
 use crate::packet::layers::ethernet_frame::ip::udp::dhcp::inter::dhcp_operations::DhcpOperations;
 use crate::packet::layers::inter::layer::Layer;
 
+const DHCP_HEADER_SIZE: usize = 240;
+
 #[derive(Clone, Debug)]
 pub struct DhcpLayer {
     op: DhcpOperations,           // Message type: 1 = Discover, 2 = Offer, etc.
@@ -29,30 +32,39 @@ impl DhcpLayer {
 
 impl Layer for DhcpLayer {
 
-    fn from_bytes(buf: &[u8]) -> Option<Self> {
-        if buf.len() < 239 {
-            return None;
+    fn from_bytes(buf: &[u8]) -> Result<Self, DecodeError> {
+        if buf.len() < DHCP_HEADER_SIZE {
+            return Err(DecodeError::Truncated {
+                layer: "DHCP",
+                offset: 0,
+                needed: DHCP_HEADER_SIZE,
+                available: buf.len()
+            });
         }
 
-        let op = DhcpOperations::from_code(buf[0]).unwrap();
+        let op = DhcpOperations::from_code(buf[0]).map_err(|_| DecodeError::UnknownCode {
+            layer: "DHCP",
+            offset: 0,
+            code: buf[0] as u32
+        })?;
         let htype = buf[1];
         let hlen = buf[2];
         let hops = buf[3];
         let mut offset = 4;
 
-        let xid = u32::from_be_bytes(buf[offset..offset + 4].try_into().ok()?);
+        let xid = u32::from_be_bytes(buf[offset..offset + 4].try_into().unwrap());
         offset += 4;
-        let secs = u16::from_be_bytes(buf[offset..offset + 2].try_into().ok()?);
+        let secs = u16::from_be_bytes(buf[offset..offset + 2].try_into().unwrap());
         offset += 2;
-        let flags = u16::from_be_bytes(buf[offset..offset + 2].try_into().ok()?);
+        let flags = u16::from_be_bytes(buf[offset..offset + 2].try_into().unwrap());
         offset += 2;
-        let ciaddr = u32::from_be_bytes(buf[offset..offset + 4].try_into().ok()?);
+        let ciaddr = u32::from_be_bytes(buf[offset..offset + 4].try_into().unwrap());
         offset += 4;
-        let yiaddr = u32::from_be_bytes(buf[offset..offset + 4].try_into().ok()?);
+        let yiaddr = u32::from_be_bytes(buf[offset..offset + 4].try_into().unwrap());
         offset += 4;
-        let siaddr = u32::from_be_bytes(buf[offset..offset + 4].try_into().ok()?);
+        let siaddr = u32::from_be_bytes(buf[offset..offset + 4].try_into().unwrap());
         offset += 4;
-        let giaddr = u32::from_be_bytes(buf[offset..offset + 4].try_into().ok()?);
+        let giaddr = u32::from_be_bytes(buf[offset..offset + 4].try_into().unwrap());
         offset += 4;
 
         let mut chaddr = [0u8; 16];
@@ -76,7 +88,7 @@ impl Layer for DhcpLayer {
             offset += 1;
         }
 
-        Some(Self {
+        Ok(Self {
             op,
             htype,
             hlen,
@@ -140,7 +152,7 @@ impl Layer for DhcpLayer {
     }
 
     fn compute_length(&mut self) -> usize {
-        self.length = 240 + self.options.len();
+        self.length = DHCP_HEADER_SIZE + self.options.len();
         self.length
     }
 
@@ -35,6 +35,7 @@ impl DhcpMessageTypes {
         }
     }
 
+    #[allow(clippy::inherent_to_string)]
     pub fn to_string(&self) -> String {
         match self {
             Self::Discover => "Discover",
@@ -23,6 +23,7 @@ impl DhcpOperations {
         }
     }
 
+    #[allow(clippy::inherent_to_string)]
     pub fn to_string(&self) -> String {
         match self {
             Self::BootRequest => "Request",
@@ -1,3 +1,4 @@
+use crate::packet::inter::decode_error::DecodeError;
 use crate::packet::layers::ethernet_frame::ip::udp::dhcp::dhcp_layer::DhcpLayer;
 use crate::packet::layers::ethernet_frame::ip::udp::inter::udp_types::UdpTypes;
 use crate::packet::layers::inter::layer::Layer;
@@ -11,20 +12,20 @@ pub enum UdpPayloads {
 
 impl UdpPayloads {
 
-    pub fn get_type_from_buf(buf: &[u8]) -> Self {
+    pub fn get_type_from_buf(buf: &[u8]) -> Result<Self, DecodeError> {
         let known_types = [
             (UdpTypes::Dhcp, 236, [0x63, 0x82, 0x53, 0x63])
         ];
 
         for (variant, position, magic) in known_types {
-            if buf.len() > position+magic.len() && &buf[position..position + magic.len()] == magic {
+            if buf.len() > position+magic.len() && buf[position..position + magic.len()] == magic {
                 return match variant {
-                    UdpTypes::Dhcp => Self::Known(variant, DhcpLayer::from_bytes(&buf).unwrap().dyn_clone()),
+                    UdpTypes::Dhcp => Ok(Self::Known(variant, Box::new(DhcpLayer::from_bytes(buf)?))),
                     _ => unreachable!()
                 };
             }
         }
 
-        Self::Unknown(buf.to_vec())
+        Ok(Self::Unknown(buf.to_vec()))
     }
 }
@@ -3,6 +3,7 @@ pub enum UdpTypes {
     Dhcp,
     Dns,
     Quick,
+    #[allow(non_camel_case_types)]
     uTp,
     BitTorrent,
     Unknown,
@@ -11,6 +12,7 @@ pub enum UdpTypes {
 
 impl UdpTypes {
 
+    #[allow(clippy::inherent_to_string)]
     pub fn to_string(&self) -> String {
         match self {
             Self::Dhcp => "DHCP",
@@ -1,3 +1,4 @@
 pub mod inter;
 pub mod udp_layer;
 pub mod dhcp;
+pub mod udp_view;
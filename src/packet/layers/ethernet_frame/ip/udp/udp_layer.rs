@@ -1,7 +1,8 @@
 use std::any::Any;
 use std::net::IpAddr;
+use crate::packet::inter::decode_error::DecodeError;
 use crate::packet::layers::ethernet_frame::ip::inter::protocols::Protocols;
-use crate::packet::layers::ethernet_frame::ip::inter::utils::calculate_checksum;
+use crate::packet::layers::ethernet_frame::ip::inter::utils::{calculate_checksum, pseudo_header};
 use crate::packet::layers::ethernet_frame::ip::udp::inter::udp_payloads::UdpPayloads;
 use crate::packet::layers::ethernet_frame::ip::udp::inter::udp_types::UdpTypes;
 use crate::packet::layers::inter::layer::Layer;
@@ -49,33 +50,12 @@ impl UdpLayer {
         self.length
     }
 
-    fn calculate_checksum(&self, source_address: IpAddr, destination_address: IpAddr) -> u16 {
-        let mut buf = vec![0; UDP_HEADER_SIZE];
-        buf.splice(0..2, self.source_port.to_be_bytes());
-        buf.splice(2..4, self.destination_port.to_be_bytes());
-        buf.splice(4..6, self.length.to_be_bytes());
-
-        match source_address {
-            IpAddr::V4(ip) => {
-                buf.extend_from_slice(&ip.octets());
-            }
-            IpAddr::V6(ip) => {
-                buf.extend_from_slice(&ip.octets());
-            }
-        }
-
-        match destination_address {
-            IpAddr::V4(ip) => {
-                buf.extend_from_slice(&ip.octets());
-            }
-            IpAddr::V6(ip) => {
-                buf.extend_from_slice(&ip.octets());
-            }
-        }
-
-        buf.push(0);
-        buf.push(Protocols::Udp.get_code());
+    pub(crate) fn calculate_checksum(&self, source_address: IpAddr, destination_address: IpAddr) -> u16 {
+        let mut buf = pseudo_header(source_address, destination_address, Protocols::Udp, self.length as usize);
+        buf.extend_from_slice(&self.source_port.to_be_bytes());
+        buf.extend_from_slice(&self.destination_port.to_be_bytes());
         buf.extend_from_slice(&self.length.to_be_bytes());
+        buf.extend_from_slice(&[0, 0]);
 
         match &self.payload {
             UdpPayloads::Known(_, payload) => {
@@ -87,7 +67,11 @@ impl UdpLayer {
             _ => {}
         }
 
-        calculate_checksum(&buf)
+        //ZERO MEANS NO CHECKSUM SO IT'S SENT AS ALL ONES
+        match calculate_checksum(&buf) {
+            0 => 0xFFFF,
+            checksum => checksum
+        }
     }
 
     pub fn compute_checksum(&mut self, source_address: IpAddr, destination_address: IpAddr) -> u16 {
@@ -137,17 +121,22 @@ impl UdpLayer {
 
 impl Layer for UdpLayer {
 
-    fn from_bytes(buf: &[u8]) -> Option<Self> {
+    fn from_bytes(buf: &[u8]) -> Result<Self, DecodeError> {
         if buf.len() < UDP_HEADER_SIZE {
-            return None;
+            return Err(DecodeError::Truncated {
+                layer: "UDP",
+                offset: 0,
+                needed: UDP_HEADER_SIZE,
+                available: buf.len()
+            });
         }
 
-        Some(Self {
+        Ok(Self {
             source_port: u16::from_be_bytes([buf[0], buf[1]]),
             destination_port: u16::from_be_bytes([buf[2], buf[3]]),
             length: u16::from_be_bytes([buf[4], buf[5]]),
             checksum: u16::from_be_bytes([buf[6], buf[7]]),
-            payload: UdpPayloads::get_type_from_buf(&buf[8..])
+            payload: UdpPayloads::get_type_from_buf(&buf[UDP_HEADER_SIZE..]).map_err(|e| e.shift(UDP_HEADER_SIZE))?
         })
     }
 
@@ -201,4 +190,18 @@ impl Layer for UdpLayer {
     fn dyn_clone(&self) -> Box<dyn Layer> {
         Box::new(self.clone())
     }
+
+    fn get_next_layer(&self) -> Option<&Box<dyn Layer>> {
+        match &self.payload {
+            UdpPayloads::Known(_, payload) => Some(payload),
+            _ => None
+        }
+    }
+
+    fn get_next_layer_mut(&mut self) -> Option<&mut Box<dyn Layer>> {
+        match &mut self.payload {
+            UdpPayloads::Known(_, payload) => Some(payload),
+            _ => None
+        }
+    }
 }
@@ -0,0 +1,56 @@
+use crate::packet::inter::decode_error::DecodeError;
+use crate::packet::layers::ethernet_frame::ip::udp::udp_layer::UdpLayer;
+use crate::packet::layers::inter::layer::Layer;
+
+const UDP_HEADER_SIZE: usize = 8;
+
+#[derive(Clone, Copy, Debug)]
+pub struct UdpView<'a> {
+    buf: &'a [u8]
+}
+
+impl<'a> UdpView<'a> {
+
+    pub fn new(buf: &'a [u8]) -> Result<Self, DecodeError> {
+        if buf.len() < UDP_HEADER_SIZE {
+            return Err(DecodeError::Truncated {
+                layer: "UDP",
+                offset: 0,
+                needed: UDP_HEADER_SIZE,
+                available: buf.len()
+            });
+        }
+
+        Ok(Self {
+            buf
+        })
+    }
+
+    pub fn get_source_port(&self) -> u16 {
+        u16::from_be_bytes([self.buf[0], self.buf[1]])
+    }
+
+    pub fn get_destination_port(&self) -> u16 {
+        u16::from_be_bytes([self.buf[2], self.buf[3]])
+    }
+
+    pub fn get_length(&self) -> u16 {
+        u16::from_be_bytes([self.buf[4], self.buf[5]])
+    }
+
+    pub fn get_checksum(&self) -> u16 {
+        u16::from_be_bytes([self.buf[6], self.buf[7]])
+    }
+
+    pub fn get_payload(&self) -> &'a [u8] {
+        &self.buf[UDP_HEADER_SIZE..]
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.buf
+    }
+
+    pub fn to_layer(&self) -> Result<UdpLayer, DecodeError> {
+        UdpLayer::from_bytes(self.buf)
+    }
+}
@@ -1,4 +1,7 @@
 pub mod inter;
 pub mod ip;
 pub mod arp;
+pub mod vlan;
+#[allow(clippy::module_inception)]
 pub mod ethernet_frame;
+pub mod ethernet_view;
@@ -0,0 +1 @@
+pub mod vlan_layer;
@@ -0,0 +1,196 @@
+use std::any::Any;
+use crate::packet::inter::decode_error::DecodeError;
+use crate::packet::layers::ethernet_frame::arp::arp_extension::ArpExtension;
+use crate::packet::layers::ethernet_frame::inter::types::Types;
+use crate::packet::layers::ethernet_frame::ip::ipv4_layer::Ipv4Layer;
+use crate::packet::layers::ethernet_frame::ip::ipv6_layer::Ipv6Layer;
+use crate::packet::layers::inter::layer::Layer;
+use crate::packet::layers::raw::raw_layer::RawLayer;
+
+const VLAN_HEADER_LEN: usize = 4;
+
+/*
+802.1Q tag, the TPID is the parent's type so only the TCI and the inner type are stored here.
+Stacked 802.1ad tags decode as one VLAN layer inside another, padding stays with the Ethernet trailer.
+*/
+#[derive(Clone, Debug)]
+pub struct VlanLayer {
+    priority: u8,
+    drop_eligible: bool,
+    vlan_id: u16,
+    _type: Types,
+    data: Option<Box<dyn Layer>>,
+    length: usize
+}
+
+impl VlanLayer {
+
+    pub fn new(vlan_id: u16, _type: Types) -> Self {
+        Self {
+            priority: 0,
+            drop_eligible: false,
+            vlan_id: vlan_id & 0x0FFF,
+            _type,
+            data: None,
+            length: VLAN_HEADER_LEN
+        }
+    }
+
+    pub fn set_priority(&mut self, priority: u8) {
+        self.priority = priority & 0x07;
+    }
+
+    pub fn get_priority(&self) -> u8 {
+        self.priority
+    }
+
+    pub fn set_drop_eligible(&mut self, drop_eligible: bool) {
+        self.drop_eligible = drop_eligible;
+    }
+
+    pub fn is_drop_eligible(&self) -> bool {
+        self.drop_eligible
+    }
+
+    pub fn set_vlan_id(&mut self, vlan_id: u16) {
+        self.vlan_id = vlan_id & 0x0FFF;
+    }
+
+    pub fn get_vlan_id(&self) -> u16 {
+        self.vlan_id
+    }
+
+    //PRIORITY, DROP ELIGIBLE AND VLAN ID PACKED THE WAY THEY ARE ON THE WIRE
+    pub fn set_tci(&mut self, tci: u16) {
+        self.priority = (tci >> 13) as u8;
+        self.drop_eligible = tci & 0x1000 != 0;
+        self.vlan_id = tci & 0x0FFF;
+    }
+
+    pub fn get_tci(&self) -> u16 {
+        ((self.priority as u16) << 13) | ((self.drop_eligible as u16) << 12) | self.vlan_id
+    }
+
+    pub fn set_type(&mut self, _type: Types) {
+        self._type = _type;
+    }
+
+    pub fn get_type(&self) -> Types {
+        self._type
+    }
+
+    pub fn set_data(&mut self, data: Box<dyn Layer>) {
+        self.length = data.len() + VLAN_HEADER_LEN;
+        self.data = Some(data);
+    }
+
+    #[allow(clippy::borrowed_box)]
+    pub fn get_data(&self) -> Option<&Box<dyn Layer>> {
+        self.data.as_ref()
+    }
+
+    pub fn get_data_mut(&mut self) -> Option<&mut Box<dyn Layer>> {
+        self.data.as_mut()
+    }
+}
+
+impl Layer for VlanLayer {
+
+    fn from_bytes(buf: &[u8]) -> Result<Self, DecodeError> {
+        if buf.len() < VLAN_HEADER_LEN {
+            return Err(DecodeError::Truncated {
+                layer: "VLAN",
+                offset: 0,
+                needed: VLAN_HEADER_LEN,
+                available: buf.len()
+            });
+        }
+
+        let tci = u16::from_be_bytes([buf[0], buf[1]]);
+        let _type = Types::from_code(u16::from_be_bytes([buf[2], buf[3]]));
+
+        let payload = &buf[VLAN_HEADER_LEN..];
+
+        let data: Option<Box<dyn Layer>> = match _type {
+            _ if payload.is_empty() => {
+                None
+            }
+            Types::IPv4 => {
+                Some(Box::new(Ipv4Layer::from_bytes(payload).map_err(|e| e.shift(VLAN_HEADER_LEN))?))
+            }
+            Types::Arp if ArpExtension::is_ethernet_ipv4(payload) => {
+                Some(Box::new(ArpExtension::from_bytes(payload).map_err(|e| e.shift(VLAN_HEADER_LEN))?))
+            }
+            Types::IPv6 => {
+                Some(Box::new(Ipv6Layer::from_bytes(payload).map_err(|e| e.shift(VLAN_HEADER_LEN))?))
+            }
+            Types::Vlan | Types::QinQ => {
+                Some(Box::new(VlanLayer::from_bytes(payload).map_err(|e| e.shift(VLAN_HEADER_LEN))?))
+            }
+            Types::Arp | Types::Broadcast | Types::Unknown(_) => {
+                Some(Box::new(RawLayer::new(_type.get_code(), payload.to_vec())))
+            }
+        };
+
+        let consumed = match &data {
+            Some(layer) => layer.len().min(payload.len()),
+            None => 0
+        };
+
+        let mut layer = Self::new(0, _type);
+        layer.set_tci(tci);
+        layer.data = data;
+        layer.length = VLAN_HEADER_LEN + consumed;
+
+        Ok(layer)
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = vec![0; VLAN_HEADER_LEN];
+        buf.splice(0..2, self.get_tci().to_be_bytes());
+        buf.splice(2..4, self._type.get_code().to_be_bytes());
+
+        if let Some(data) = &self.data {
+            buf.extend(data.to_bytes());
+        }
+
+        buf
+    }
+
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn compute_length(&mut self) -> usize {
+        self.length = match &self.data {
+            Some(layer) => {
+                layer.len() + VLAN_HEADER_LEN
+            }
+            None => {
+                VLAN_HEADER_LEN
+            }
+        };
+
+        self.length
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn dyn_clone(&self) -> Box<dyn Layer> {
+        Box::new(self.clone())
+    }
+
+    fn get_next_layer(&self) -> Option<&Box<dyn Layer>> {
+        self.data.as_ref()
+    }
+
+    fn get_next_layer_mut(&mut self) -> Option<&mut Box<dyn Layer>> {
+        self.data.as_mut()
+    }
+}
@@ -1,14 +1,19 @@
 use std::any::Any;
 use std::fmt::Debug;
+use crate::packet::inter::decode_error::DecodeError;
 
 pub trait Layer: Send + Debug {
 
-    fn from_bytes(buf: &[u8]) -> Option<Self> where Self: Sized;
+    fn from_bytes(buf: &[u8]) -> Result<Self, DecodeError> where Self: Sized;
 
     fn to_bytes(&self) -> Vec<u8>;
 
     fn len(&self) -> usize;
 
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     fn compute_length(&mut self) -> usize;
 
     fn as_any(&self) -> &dyn Any;
@@ -16,6 +21,16 @@ pub trait Layer: Send + Debug {
     fn as_any_mut(&mut self) -> &mut dyn Any;
 
     fn dyn_clone(&self) -> Box<dyn Layer>;
+
+    //THE ENCAPSULATED LAYER, LEAF LAYERS KEEP THE DEFAULT
+    #[allow(clippy::borrowed_box)]
+    fn get_next_layer(&self) -> Option<&Box<dyn Layer>> {
+        None
+    }
+
+    fn get_next_layer_mut(&mut self) -> Option<&mut Box<dyn Layer>> {
+        None
+    }
 }
 
 impl Clone for Box<dyn Layer> {
@@ -24,3 +39,48 @@ impl Clone for Box<dyn Layer> {
         self.dyn_clone()
     }
 }
+
+impl dyn Layer {
+
+    pub fn is<T: Layer + 'static>(&self) -> bool {
+        self.as_any().is::<T>()
+    }
+
+    //SEARCHES THIS LAYER AND EVERYTHING BELOW IT, FIRST MATCH FROM THE TOP WINS
+    pub fn layer<T: Layer + 'static>(&self) -> Option<&T> {
+        self.layers().find_map(|layer| layer.as_any().downcast_ref::<T>())
+    }
+
+    pub fn layer_mut<T: Layer + 'static>(&mut self) -> Option<&mut T> {
+        let mut current = self;
+
+        loop {
+            if current.is::<T>() {
+                return current.as_any_mut().downcast_mut::<T>();
+            }
+
+            current = current.get_next_layer_mut()?.as_mut();
+        }
+    }
+
+    pub fn layers(&self) -> Layers<'_> {
+        Layers {
+            next: Some(self)
+        }
+    }
+}
+
+pub struct Layers<'a> {
+    next: Option<&'a (dyn Layer + 'static)>
+}
+
+impl<'a> Iterator for Layers<'a> {
+
+    type Item = &'a (dyn Layer + 'static);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        self.next = current.get_next_layer().map(|layer| layer.as_ref());
+        Some(current)
+    }
+}
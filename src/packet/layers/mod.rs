@@ -1,2 +1,3 @@
 pub mod inter;
 pub mod ethernet_frame;
+pub mod raw;
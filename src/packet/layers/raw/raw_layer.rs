@@ -0,0 +1,78 @@
+use std::any::Any;
+use crate::packet::inter::decode_error::DecodeError;
+use crate::packet::layers::inter::layer::Layer;
+
+/*
+Holds the bytes of a payload we don't decode, code is the EtherType or IP protocol number
+that pointed at it so the parent can still be re-encoded exactly.
+*/
+
+#[derive(Clone, Debug)]
+pub struct RawLayer {
+    code: u16,
+    data: Vec<u8>
+}
+
+impl RawLayer {
+
+    pub fn new(code: u16, data: Vec<u8>) -> Self {
+        Self {
+            code,
+            data
+        }
+    }
+
+    pub fn set_code(&mut self, code: u16) {
+        self.code = code;
+    }
+
+    pub fn get_code(&self) -> u16 {
+        self.code
+    }
+
+    pub fn set_data(&mut self, data: Vec<u8>) {
+        self.data = data;
+    }
+
+    pub fn get_data(&self) -> &Vec<u8> {
+        &self.data
+    }
+
+    pub fn get_data_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.data
+    }
+}
+
+impl Layer for RawLayer {
+
+    fn from_bytes(buf: &[u8]) -> Result<Self, DecodeError> {
+        Ok(Self {
+            code: 0,
+            data: buf.to_vec()
+        })
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.data.clone()
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn compute_length(&mut self) -> usize {
+        self.data.len()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn dyn_clone(&self) -> Box<dyn Layer> {
+        Box::new(self.clone())
+    }
+}
@@ -1,3 +1,4 @@
 pub mod inter;
 pub mod layers;
+#[allow(clippy::module_inception)]
 pub mod packet;
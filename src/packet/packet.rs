@@ -1,37 +1,80 @@
+use std::net::IpAddr;
 use std::time::{SystemTime, UNIX_EPOCH};
+use crate::packet::inter::decode_error::DecodeError;
 use crate::packet::inter::interfaces::Interfaces;
+use crate::packet::inter::packet_types::PacketTypes;
 use crate::packet::layers::ethernet_frame::ethernet_frame::EthernetFrame;
-use crate::packet::layers::inter::layer::Layer;
+use crate::packet::layers::ethernet_frame::ip::icmp::icmp_layer::IcmpLayer;
+use crate::packet::layers::ethernet_frame::ip::icmpv6::icmpv6_layer::Icmpv6Layer;
+use crate::packet::layers::ethernet_frame::ip::ipv4_layer::Ipv4Layer;
+use crate::packet::layers::ethernet_frame::ip::ipv6_layer::Ipv6Layer;
+use crate::packet::layers::ethernet_frame::ip::tcp::tcp_layer::TcpLayer;
+use crate::packet::layers::ethernet_frame::ip::udp::udp_layer::UdpLayer;
+use crate::packet::layers::inter::layer::{Layer, Layers};
 
 #[derive(Debug, Clone)]
 pub struct Packet {
     interface: Interfaces,
     frame: Box<dyn Layer>,
     frame_time: u128,
-    length: usize
+    length: usize,
+    original_length: usize,
+    packet_type: Option<PacketTypes>,
+    if_index: Option<i32>,
+    hardware_type: Option<u16>,
+    undecoded: Vec<u8>
 }
 
 impl Packet {
 
-    pub fn new(interface: Interfaces, frame_time: u128, data: &[u8]) -> Self {
-        let frame = match interface {
-            Interfaces::Ethernet => {
-                EthernetFrame::from_bytes(data).unwrap().dyn_clone()
-            }
-            Interfaces::WiFi => {
-                todo!()
-            }
-            Interfaces::Bluetooth => {
-                todo!()
+    pub fn new(interface: Interfaces, frame_time: u128, data: &[u8]) -> Result<Self, DecodeError> {
+        Self::with_original_length(interface, frame_time, data, data.len())
+    }
+
+    /*
+    Original length is how long the frame was on the wire, data can be shorter when the capture was cut at the snap length.
+    A cut frame is decoded as far as the captured bytes go, the layer that runs off the end is kept as undecoded bytes
+    so to_bytes still gives back exactly what was captured.
+    */
+    pub fn with_original_length(interface: Interfaces, frame_time: u128, data: &[u8], original_length: usize) -> Result<Self, DecodeError> {
+        let mut captured = data;
+
+        let frame = loop {
+            match Self::decode_frame(interface, captured) {
+                Ok(frame) => break frame,
+                Err(DecodeError::Truncated { offset, .. }) if original_length > data.len() && offset > 0 && offset < captured.len() => {
+                    captured = &captured[..offset];
+                }
+                Err(e) => return Err(e)
             }
         };
 
-        Self {
+        Ok(Self {
             interface,
             frame,
             frame_time,
-            length: data.len()
-        }
+            length: data.len(),
+            original_length: original_length.max(data.len()),
+            packet_type: None,
+            if_index: None,
+            hardware_type: None,
+            undecoded: data[captured.len()..].to_vec()
+        })
+    }
+
+    fn decode_frame(interface: Interfaces, data: &[u8]) -> Result<Box<dyn Layer>, DecodeError> {
+        Ok(match interface {
+            Interfaces::Ethernet => {
+                Box::new(EthernetFrame::from_bytes(data)?)
+            }
+            Interfaces::WiFi | Interfaces::Bluetooth => {
+                return Err(DecodeError::UnknownCode {
+                    layer: "Packet",
+                    offset: 0,
+                    code: interface.get_code()
+                });
+            }
+        })
     }
 
     pub fn set_interface(&mut self, interface: Interfaces) {
@@ -44,9 +87,12 @@ impl Packet {
 
     pub fn set_frame(&mut self, frame: Box<dyn Layer>) {
         self.length = frame.len();
+        self.original_length = self.length;
         self.frame = frame;
+        self.undecoded.clear();
     }
 
+    #[allow(clippy::borrowed_box)]
     pub fn get_frame(&self) -> &Box<dyn Layer> {
         &self.frame
     }
@@ -55,6 +101,33 @@ impl Packet {
         &mut self.frame
     }
 
+    //ONLY KNOWN FOR LIVE CAPTURES, FILES DON'T RECORD IT
+    pub fn set_packet_type(&mut self, packet_type: Option<PacketTypes>) {
+        self.packet_type = packet_type;
+    }
+
+    pub fn get_packet_type(&self) -> Option<PacketTypes> {
+        self.packet_type
+    }
+
+    pub fn set_if_index(&mut self, if_index: Option<i32>) {
+        self.if_index = if_index;
+    }
+
+    pub fn get_if_index(&self) -> Option<i32> {
+        self.if_index
+    }
+
+    //ARPHRD_* FROM if_arp.h, 1 FOR ETHERNET AND 772 FOR LOOPBACK
+    pub fn set_hardware_type(&mut self, hardware_type: Option<u16>) {
+        self.hardware_type = hardware_type;
+    }
+
+    pub fn get_hardware_type(&self) -> Option<u16> {
+        self.hardware_type
+    }
+
+    //NANOSECONDS SINCE THE UNIX EPOCH
     pub fn set_frame_time(&mut self, frame_time: u128) {
         self.frame_time = frame_time;
     }
@@ -63,26 +136,202 @@ impl Packet {
         self.frame_time
     }
 
+    //HOW MANY BYTES WERE CAPTURED
+    pub fn get_captured_length(&self) -> usize {
+        self.length
+    }
+
+    //HOW LONG THE FRAME WAS ON THE WIRE
+    pub fn get_original_length(&self) -> usize {
+        self.original_length
+    }
+
+    //CAPTURED BYTES AFTER THE LAST LAYER THAT COULD BE DECODED, ONLY SET FOR TRUNCATED CAPTURES
+    pub fn get_undecoded(&self) -> &[u8] {
+        &self.undecoded
+    }
+
+    pub fn is_truncated(&self) -> bool {
+        self.original_length > self.length
+    }
+
+    pub fn layer<T: Layer + 'static>(&self) -> Option<&T> {
+        self.frame.layer::<T>()
+    }
+
+    pub fn layer_mut<T: Layer + 'static>(&mut self) -> Option<&mut T> {
+        self.frame.layer_mut::<T>()
+    }
+
+    //EVERY LAYER FROM THE FRAME DOWN
+    pub fn layers(&self) -> Layers<'_> {
+        self.frame.layers()
+    }
+
     pub fn to_bytes(&self) -> Vec<u8> {
-        self.frame.to_bytes()
+        let mut buf = self.frame.to_bytes();
+        buf.extend_from_slice(&self.undecoded);
+        buf
     }
 
     pub fn len(&self) -> usize {
-        self.frame.len()
+        self.frame.len() + self.undecoded.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 
     pub fn compute_length(&mut self) -> usize {
-        let length = self.frame.compute_length();
+        let length = self.frame.compute_length() + self.undecoded.len();
         self.length = length;
+        self.original_length = length;
         length
     }
+
+    /*
+    Checks every IPv4, ICMP, TCP, UDP and ICMPv6 checksum, decoding doesn't as captured outgoing packets often carry
+    checksums the NIC fills in later. Truncated captures only have their IPv4 headers checked, the rest covers bytes that weren't captured.
+    */
+    pub fn validate(&self) -> Result<(), DecodeError> {
+        validate_layer(self.frame.as_ref(), 0, None, self.is_truncated())
+    }
+
+    //RECOMPUTES EVERY LENGTH AND CHECKSUM FROM THE INNERMOST LAYER OUT
+    pub fn finalize(&mut self) {
+        finalize_layer(self.frame.as_mut(), None);
+        self.length = self.len();
+        self.original_length = self.length;
+    }
+}
+
+fn finalize_layer(layer: &mut dyn Layer, addresses: Option<(IpAddr, IpAddr)>) {
+    let child_addresses = if let Some(ipv4) = layer.as_any().downcast_ref::<Ipv4Layer>() {
+        Some((IpAddr::V4(ipv4.get_source_address()), IpAddr::V4(ipv4.get_destination_address())))
+
+    } else if let Some(ipv6) = layer.as_any().downcast_ref::<Ipv6Layer>() {
+        Some((IpAddr::V6(ipv6.get_source_address()), IpAddr::V6(ipv6.get_destination_address())))
+
+    } else {
+        addresses
+    };
+
+    if let Some(next) = layer.get_next_layer_mut() {
+        finalize_layer(next.as_mut(), child_addresses);
+    }
+
+    layer.compute_length();
+
+    let layer = layer.as_any_mut();
+
+    if let Some(ipv4) = layer.downcast_mut::<Ipv4Layer>() {
+        ipv4.compute_checksum();
+        return;
+    }
+
+    if let Some(icmp) = layer.downcast_mut::<IcmpLayer>() {
+        icmp.compute_checksum();
+        return;
+    }
+
+    //TRANSPORT CHECKSUMS NEED THE ENCLOSING IP ADDRESSES
+    let (source_address, destination_address) = match addresses {
+        Some(addresses) => addresses,
+        None => return
+    };
+
+    if let Some(tcp) = layer.downcast_mut::<TcpLayer>() {
+        tcp.compute_checksum(source_address, destination_address);
+
+    } else if let Some(udp) = layer.downcast_mut::<UdpLayer>() {
+        udp.compute_checksum(source_address, destination_address);
+
+    } else if let Some(icmpv6) = layer.downcast_mut::<Icmpv6Layer>() {
+        if let (IpAddr::V6(source_address), IpAddr::V6(destination_address)) = (source_address, destination_address) {
+            icmpv6.compute_checksum(source_address, destination_address);
+        }
+    }
+}
+
+fn validate_layer(layer: &dyn Layer, offset: usize, addresses: Option<(IpAddr, IpAddr)>, truncated: bool) -> Result<(), DecodeError> {
+    let any = layer.as_any();
+    let mut child_addresses = addresses;
+
+    let checksum = if let Some(ipv4) = any.downcast_ref::<Ipv4Layer>() {
+        child_addresses = Some((IpAddr::V4(ipv4.get_source_address()), IpAddr::V4(ipv4.get_destination_address())));
+        Some(("IPv4", ipv4.calculate_checksum(), ipv4.get_checksum()))
+
+    } else if let Some(ipv6) = any.downcast_ref::<Ipv6Layer>() {
+        child_addresses = Some((IpAddr::V6(ipv6.get_source_address()), IpAddr::V6(ipv6.get_destination_address())));
+        None
+
+    } else if truncated {
+        None
+
+    } else if let Some(icmp) = any.downcast_ref::<IcmpLayer>() {
+        Some(("ICMP", icmp.calculate_checksum(), icmp.get_checksum()))
+
+    } else if let Some((source_address, destination_address)) = addresses {
+        if let Some(tcp) = any.downcast_ref::<TcpLayer>() {
+            Some(("TCP", tcp.calculate_checksum(source_address, destination_address), tcp.get_checksum()))
+
+        //ZERO OVER IPv4 MEANS THE SENDER DIDN'T COMPUTE ONE
+        } else if let Some(udp) = any.downcast_ref::<UdpLayer>() {
+            match udp.get_checksum() == 0 && source_address.is_ipv4() {
+                true => None,
+                false => Some(("UDP", udp.calculate_checksum(source_address, destination_address), udp.get_checksum()))
+            }
+
+        } else if let Some(icmpv6) = any.downcast_ref::<Icmpv6Layer>() {
+            match (source_address, destination_address) {
+                (IpAddr::V6(source_address), IpAddr::V6(destination_address)) => {
+                    Some(("ICMPv6", icmpv6.calculate_checksum(source_address, destination_address), icmpv6.get_checksum()))
+                }
+                _ => None
+            }
+
+        } else {
+            None
+        }
+
+    } else {
+        None
+    };
+
+    if let Some((name, expected, found)) = checksum {
+        if expected != found {
+            return Err(DecodeError::BadChecksum {
+                layer: name,
+                offset,
+                expected,
+                found
+            });
+        }
+    }
+
+    if let Some(next) = layer.get_next_layer() {
+        //THE HEADER IS WHATEVER THE LAYER WRITES AROUND ITS CHILD, ETHERNET ALSO WRITES ITS TRAILER AFTER IT
+        let trailer = any.downcast_ref::<EthernetFrame>().map_or(0, |frame| frame.get_trailer().len());
+        let header = layer.to_bytes().len().saturating_sub(next.to_bytes().len() + trailer);
+        validate_layer(next.as_ref(), offset + header, child_addresses, truncated)?;
+    }
+
+    Ok(())
 }
 
-pub fn decode_packet(interface: Interfaces, data: &[u8]) -> Packet {
+//STAMPS THE PACKET WITH THE CURRENT TIME, CAPTURES USE THE KERNEL TIMESTAMP INSTEAD
+pub fn decode_packet(interface: Interfaces, data: &[u8]) -> Result<Packet, DecodeError> {
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .expect("Time went backwards")
-        .as_millis();
+        .as_nanos();
 
     Packet::new(interface, now, data)
 }
+
+//LIKE decode_packet BUT BAD CHECKSUMS ARE ERRORS TOO
+pub fn decode_packet_checked(interface: Interfaces, data: &[u8]) -> Result<Packet, DecodeError> {
+    let packet = decode_packet(interface, data)?;
+    packet.validate()?;
+    Ok(packet)
+}
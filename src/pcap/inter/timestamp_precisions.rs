@@ -0,0 +1,41 @@
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum TimestampPrecisions {
+    Micro,
+    Nano
+}
+
+impl TimestampPrecisions {
+
+    pub fn from_magic(magic: u32) -> Result<Self, String> {
+        for c in [Self::Micro, Self::Nano] {
+            if c.get_magic() == magic {
+                return Ok(c);
+            }
+        }
+
+        Err(format!("Couldn't find for magic: {:#x}", magic))
+    }
+
+    pub fn get_magic(&self) -> u32 {
+        match self {
+            Self::Micro => 0xa1b2c3d4,
+            Self::Nano => 0xa1b23c4d
+        }
+    }
+
+    //SUB-SECOND UNITS PER SECOND
+    pub fn get_units(&self) -> u32 {
+        match self {
+            Self::Micro => 1_000_000,
+            Self::Nano => 1_000_000_000
+        }
+    }
+
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        match self {
+            Self::Micro => "Microsecond",
+            Self::Nano => "Nanosecond"
+        }.to_string()
+    }
+}
@@ -0,0 +1,3 @@
+pub mod inter;
+pub mod pcap_reader;
+pub mod pcap_writer;
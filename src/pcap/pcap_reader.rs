@@ -0,0 +1,205 @@
+use std::io;
+use std::io::Read;
+use crate::bpf::bpf_program::BpfProgram;
+use crate::bpf::inter::filter_error::FilterError;
+use crate::packet::inter::interfaces::Interfaces;
+use crate::packet::packet::Packet;
+use crate::pcap::inter::timestamp_precisions::TimestampPrecisions;
+
+#[derive(Debug)]
+pub struct PcapReader<R: Read> {
+    reader: R,
+    interface: Interfaces,
+    precision: TimestampPrecisions,
+    big_endian: bool,
+    version_major: u16,
+    version_minor: u16,
+    snap_length: u32,
+    fcs_length: Option<u8>,
+    filter: Option<BpfProgram>
+}
+
+impl<R: Read> PcapReader<R> {
+
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut buf = [0u8; 24];
+        reader.read_exact(&mut buf)?;
+
+        let magic = [buf[0], buf[1], buf[2], buf[3]];
+
+        let (precision, big_endian) = match TimestampPrecisions::from_magic(u32::from_le_bytes(magic)) {
+            Ok(precision) => (precision, false),
+            Err(_) => {
+                match TimestampPrecisions::from_magic(u32::from_be_bytes(magic)) {
+                    Ok(precision) => (precision, true),
+                    Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e))
+                }
+            }
+        };
+
+        let read_u16 = |b: [u8; 2]| if big_endian { u16::from_be_bytes(b) } else { u16::from_le_bytes(b) };
+        let read_u32 = |b: [u8; 4]| if big_endian { u32::from_be_bytes(b) } else { u32::from_le_bytes(b) };
+
+        //LINK TYPE IS THE LOW 16 BITS, BIT 26 SAYS BITS 28-31 HOLD THE FCS LENGTH IN 16 BIT WORDS
+        let link_type = read_u32([buf[20], buf[21], buf[22], buf[23]]);
+        let fcs_length = match link_type & 0x04000000 {
+            0 => None,
+            _ => Some(((link_type >> 28) * 2) as u8)
+        };
+
+        let interface = Interfaces::from_code(link_type & 0xFFFF)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        Ok(Self {
+            reader,
+            interface,
+            precision,
+            big_endian,
+            version_major: read_u16([buf[4], buf[5]]),
+            version_minor: read_u16([buf[6], buf[7]]),
+            snap_length: read_u32([buf[16], buf[17], buf[18], buf[19]]),
+            fcs_length,
+            filter: None
+        })
+    }
+
+    pub fn get_interface(&self) -> Interfaces {
+        self.interface
+    }
+
+    pub fn get_precision(&self) -> TimestampPrecisions {
+        self.precision
+    }
+
+    pub fn is_big_endian(&self) -> bool {
+        self.big_endian
+    }
+
+    pub fn get_version(&self) -> (u16, u16) {
+        (self.version_major, self.version_minor)
+    }
+
+    pub fn get_snap_length(&self) -> u32 {
+        self.snap_length
+    }
+
+    //BYTES OF FCS AT THE END OF EVERY RECORD, NONE WHEN THE HEADER DOESN'T SAY
+    pub fn get_fcs_length(&self) -> Option<u8> {
+        self.fcs_length
+    }
+
+    //RECORDS THE FILTER REJECTS ARE SKIPPED, MATCHES ARE TRUNCATED TO WHAT IT RETURNS LIKE THE KERNEL DOES
+    pub fn set_filter(&mut self, expression: &str) -> Result<(), FilterError> {
+        self.set_program(BpfProgram::compile(expression)?)
+    }
+
+    pub fn set_program(&mut self, program: BpfProgram) -> Result<(), FilterError> {
+        program.verify()?;
+        self.filter = Some(program);
+        Ok(())
+    }
+
+    pub fn clear_filter(&mut self) {
+        self.filter = None;
+    }
+
+    pub fn get_filter(&self) -> Option<&BpfProgram> {
+        self.filter.as_ref()
+    }
+
+    pub fn next_packet(&mut self) -> io::Result<Packet> {
+        match self.next_filtered_record()? {
+            Some((frame_time, original_length, data)) => {
+                Ok(Packet::with_original_length(self.interface, frame_time, &data, original_length as usize)?)
+            }
+            None => Err(io::Error::new(io::ErrorKind::UnexpectedEof, "No more packets"))
+        }
+    }
+
+    //RETURNS FRAME TIME IN NANOSECONDS AND THE CAPTURED BYTES, NONE AT A CLEAN END OF FILE
+    pub fn next_record(&mut self) -> io::Result<Option<(u128, Vec<u8>)>> {
+        Ok(self.next_filtered_record()?.map(|(frame_time, _, data)| (frame_time, data)))
+    }
+
+    fn next_filtered_record(&mut self) -> io::Result<Option<(u128, u32, Vec<u8>)>> {
+        loop {
+            let (frame_time, original_length, mut data) = match self.read_record()? {
+                Some(record) => record,
+                None => return Ok(None)
+            };
+
+            match &self.filter {
+                Some(filter) => {
+                    let keep = filter.run(&data, original_length) as usize;
+                    if keep > 0 {
+                        data.truncate(keep);
+                        return Ok(Some((frame_time, original_length, data)));
+                    }
+                }
+                None => return Ok(Some((frame_time, original_length, data)))
+            }
+        }
+    }
+
+    fn read_record(&mut self) -> io::Result<Option<(u128, u32, Vec<u8>)>> {
+        let mut buf = [0u8; 16];
+
+        let mut read = 0;
+        while read < buf.len() {
+            match self.reader.read(&mut buf[read..]) {
+                Ok(0) => break,
+                Ok(n) => read += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e)
+            }
+        }
+
+        if read == 0 {
+            return Ok(None);
+        }
+
+        if read < buf.len() {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "Truncated record header"));
+        }
+
+        let seconds = self.read_u32([buf[0], buf[1], buf[2], buf[3]]) as u128;
+        let fraction = self.read_u32([buf[4], buf[5], buf[6], buf[7]]) as u128;
+        let captured = self.read_u32([buf[8], buf[9], buf[10], buf[11]]) as usize;
+        let original_length = self.read_u32([buf[12], buf[13], buf[14], buf[15]]);
+
+        if captured > self.snap_length.max(262144) as usize {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Record length exceeds snap length"));
+        }
+
+        let mut data = vec![0u8; captured];
+        self.reader.read_exact(&mut data)?;
+
+        let frame_time = seconds * 1_000_000_000 + fraction * (1_000_000_000 / self.precision.get_units()) as u128;
+
+        Ok(Some((frame_time, original_length, data)))
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn read_u32(&self, buf: [u8; 4]) -> u32 {
+        match self.big_endian {
+            true => u32::from_be_bytes(buf),
+            false => u32::from_le_bytes(buf)
+        }
+    }
+}
+
+impl<R: Read> Iterator for PcapReader<R> {
+
+    type Item = io::Result<Packet>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_filtered_record() {
+            Ok(Some((frame_time, original_length, data))) => Some(Packet::with_original_length(self.interface, frame_time, &data, original_length as usize).map_err(io::Error::from)),
+            Ok(None) => None,
+            Err(e) => Some(Err(e))
+        }
+    }
+}
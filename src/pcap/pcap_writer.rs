@@ -0,0 +1,107 @@
+use std::io;
+use std::io::Write;
+use crate::packet::inter::interfaces::Interfaces;
+use crate::packet::packet::Packet;
+use crate::pcap::inter::timestamp_precisions::TimestampPrecisions;
+
+pub const PCAP_VERSION_MAJOR: u16 = 2;
+pub const PCAP_VERSION_MINOR: u16 = 4;
+pub const PCAP_DEFAULT_SNAP_LENGTH: u32 = 262144;
+
+/*
+Writes the classic libpcap format, headers are written in little endian:
+
+GLOBAL HEADER (24)
+| magic (4) | major (2) | minor (2) | thiszone (4) | sigfigs (4) | snaplen (4) | network (4) |
+
+RECORD HEADER (16) + DATA
+| ts_sec (4) | ts_frac (4) | incl_len (4) | orig_len (4) | data (incl_len) |
+*/
+
+#[derive(Debug)]
+pub struct PcapWriter<W: Write> {
+    writer: W,
+    interface: Interfaces,
+    precision: TimestampPrecisions,
+    snap_length: u32
+}
+
+impl<W: Write> PcapWriter<W> {
+
+    pub fn new(writer: W, interface: Interfaces, precision: TimestampPrecisions) -> io::Result<Self> {
+        Self::with_snap_length(writer, interface, precision, PCAP_DEFAULT_SNAP_LENGTH)
+    }
+
+    pub fn with_snap_length(mut writer: W, interface: Interfaces, precision: TimestampPrecisions, snap_length: u32) -> io::Result<Self> {
+        let mut buf = vec![0; 24];
+
+        buf.splice(0..4, precision.get_magic().to_le_bytes());
+        buf.splice(4..6, PCAP_VERSION_MAJOR.to_le_bytes());
+        buf.splice(6..8, PCAP_VERSION_MINOR.to_le_bytes());
+        buf.splice(16..20, snap_length.to_le_bytes());
+        buf.splice(20..24, interface.get_code().to_le_bytes());
+
+        writer.write_all(&buf)?;
+
+        Ok(Self {
+            writer,
+            interface,
+            precision,
+            snap_length
+        })
+    }
+
+    pub fn get_interface(&self) -> Interfaces {
+        self.interface
+    }
+
+    pub fn get_precision(&self) -> TimestampPrecisions {
+        self.precision
+    }
+
+    pub fn get_snap_length(&self) -> u32 {
+        self.snap_length
+    }
+
+    pub fn write_packet(&mut self, packet: &Packet) -> io::Result<()> {
+        if *packet.get_interface() != self.interface {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Packet interface doesn't match the file link type"));
+        }
+
+        let data = packet.to_bytes();
+        let original_length = packet.get_original_length().max(data.len());
+        self.write_record(packet.get_frame_time(), &data, original_length)
+    }
+
+    pub fn write_bytes(&mut self, frame_time: u128, data: &[u8]) -> io::Result<()> {
+        self.write_record(frame_time, data, data.len())
+    }
+
+    fn write_record(&mut self, frame_time: u128, data: &[u8], original_length: usize) -> io::Result<()> {
+        //FRAME TIME IS IN NANOSECONDS
+        let seconds = (frame_time / 1_000_000_000) as u32;
+        let fraction = ((frame_time % 1_000_000_000) as u32) / (1_000_000_000 / self.precision.get_units());
+        let captured = data.len().min(self.snap_length as usize);
+
+        let mut buf = vec![0; 16];
+        buf.splice(0..4, seconds.to_le_bytes());
+        buf.splice(4..8, fraction.to_le_bytes());
+        buf.splice(8..12, (captured as u32).to_le_bytes());
+        buf.splice(12..16, (original_length as u32).to_le_bytes());
+
+        self.writer.write_all(&buf)?;
+        self.writer.write_all(&data[..captured])
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
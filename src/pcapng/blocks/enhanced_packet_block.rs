@@ -0,0 +1,97 @@
+use std::io;
+use crate::pcapng::inter::block_option::{BlockOption, OPT_COMMENT};
+use crate::pcapng::inter::block_types::BlockTypes;
+use crate::pcapng::inter::utils::{encode_block, padded_len, read_u32, truncated};
+
+pub const EPB_FLAGS: u16 = 2;
+
+const ENHANCED_PACKET_BODY_LEN: usize = 20;
+
+#[derive(Clone, Debug)]
+pub struct EnhancedPacketBlock {
+    interface_id: u32,
+    timestamp: u64,
+    original_length: u32,
+    data: Vec<u8>,
+    options: Vec<BlockOption>
+}
+
+impl EnhancedPacketBlock {
+
+    pub fn new(interface_id: u32, timestamp: u64, original_length: u32, data: Vec<u8>) -> Self {
+        Self {
+            interface_id,
+            timestamp,
+            original_length,
+            data,
+            options: Vec::new()
+        }
+    }
+
+    pub fn get_interface_id(&self) -> u32 {
+        self.interface_id
+    }
+
+    //TIMESTAMP IS IN THE UNITS OF THE INTERFACE IF_TSRESOL
+    pub fn get_timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    pub fn get_original_length(&self) -> u32 {
+        self.original_length
+    }
+
+    pub fn get_data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn add_option(&mut self, option: BlockOption) {
+        self.options.push(option);
+    }
+
+    pub fn get_options(&self) -> &Vec<BlockOption> {
+        &self.options
+    }
+
+    pub fn get_comments(&self) -> Vec<String> {
+        self.options.iter().filter(|o| o.get_code() == OPT_COMMENT).map(|o| o.get_string()).collect()
+    }
+
+    pub fn from_bytes(buf: &[u8], big_endian: bool) -> io::Result<Self> {
+        if buf.len() < ENHANCED_PACKET_BODY_LEN {
+            return Err(truncated("enhanced packet block"));
+        }
+
+        let captured = read_u32(&buf[12..], big_endian) as usize;
+        let options_offset = ENHANCED_PACKET_BODY_LEN + padded_len(captured);
+
+        if ENHANCED_PACKET_BODY_LEN + captured > buf.len() {
+            return Err(truncated("enhanced packet block"));
+        }
+
+        let timestamp = ((read_u32(&buf[4..], big_endian) as u64) << 32) | read_u32(&buf[8..], big_endian) as u64;
+
+        Ok(Self {
+            interface_id: read_u32(buf, big_endian),
+            timestamp,
+            original_length: read_u32(&buf[16..], big_endian),
+            data: buf[ENHANCED_PACKET_BODY_LEN..ENHANCED_PACKET_BODY_LEN + captured].to_vec(),
+            options: BlockOption::options_from_bytes(&buf[options_offset.min(buf.len())..], big_endian)?
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = vec![0; ENHANCED_PACKET_BODY_LEN];
+
+        buf.splice(0..4, self.interface_id.to_le_bytes());
+        buf.splice(4..8, ((self.timestamp >> 32) as u32).to_le_bytes());
+        buf.splice(8..12, (self.timestamp as u32).to_le_bytes());
+        buf.splice(12..16, (self.data.len() as u32).to_le_bytes());
+        buf.splice(16..20, self.original_length.to_le_bytes());
+        buf.extend_from_slice(&self.data);
+        buf.resize(padded_len(buf.len()), 0);
+        buf.extend(BlockOption::options_to_bytes(&self.options));
+
+        encode_block(BlockTypes::EnhancedPacket.get_code(), &buf)
+    }
+}
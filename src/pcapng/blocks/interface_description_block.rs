@@ -0,0 +1,114 @@
+use std::io;
+use crate::devices::Device;
+use crate::packet::inter::interfaces::Interfaces;
+use crate::pcapng::inter::block_option::{BlockOption, OPT_COMMENT};
+use crate::pcapng::inter::block_types::BlockTypes;
+use crate::pcapng::inter::utils::{encode_block, read_u16, read_u32, truncated};
+
+pub const IF_NAME: u16 = 2;
+pub const IF_DESCRIPTION: u16 = 3;
+pub const IF_TSRESOL: u16 = 9;
+
+const INTERFACE_DESCRIPTION_BODY_LEN: usize = 8;
+
+#[derive(Clone, Debug)]
+pub struct InterfaceDescriptionBlock {
+    interface: Interfaces,
+    snap_length: u32,
+    options: Vec<BlockOption>
+}
+
+impl InterfaceDescriptionBlock {
+
+    pub fn new(interface: Interfaces, snap_length: u32) -> Self {
+        Self {
+            interface,
+            snap_length,
+            options: Vec::new()
+        }
+    }
+
+    pub fn from_device(device: &Device, snap_length: u32) -> Self {
+        let mut block = Self::new(device.get_interface(), snap_length);
+        block.add_option(BlockOption::new(IF_NAME, device.get_name().into_bytes()));
+
+        if let Some(description) = device.get_description() {
+            block.add_option(BlockOption::new(IF_DESCRIPTION, description.trim().as_bytes().to_vec()));
+        }
+
+        block
+    }
+
+    pub fn get_interface(&self) -> Interfaces {
+        self.interface
+    }
+
+    pub fn get_snap_length(&self) -> u32 {
+        self.snap_length
+    }
+
+    pub fn get_name(&self) -> Option<String> {
+        self.options.iter().find(|o| o.get_code() == IF_NAME).map(|o| o.get_string())
+    }
+
+    //UNITS OF THE TIMESTAMP PER SECOND, DEFAULT IS MICROSECONDS. PARSED AND WRITTEN BLOCKS NEVER HAVE A RESOLUTION TOO FINE FOR u128
+    pub fn get_timestamp_units(&self) -> u128 {
+        self.timestamp_units().unwrap_or(1_000_000)
+    }
+
+    //NONE WHEN if_tsresol IS TOO FINE FOR u128
+    pub(crate) fn timestamp_units(&self) -> Option<u128> {
+        match self.options.iter().find(|o| o.get_code() == IF_TSRESOL).and_then(|o| o.get_value().first()) {
+            Some(resolution) if resolution & 0x80 != 0 => {
+                Some(1u128 << (resolution & 0x7F))
+            }
+            Some(resolution) => {
+                10u128.checked_pow(*resolution as u32)
+            }
+            None => Some(1_000_000)
+        }
+    }
+
+    pub fn add_option(&mut self, option: BlockOption) {
+        self.options.push(option);
+    }
+
+    pub fn get_options(&self) -> &Vec<BlockOption> {
+        &self.options
+    }
+
+    pub fn get_comments(&self) -> Vec<String> {
+        self.options.iter().filter(|o| o.get_code() == OPT_COMMENT).map(|o| o.get_string()).collect()
+    }
+
+    pub fn from_bytes(buf: &[u8], big_endian: bool) -> io::Result<Self> {
+        if buf.len() < INTERFACE_DESCRIPTION_BODY_LEN {
+            return Err(truncated("interface description block"));
+        }
+
+        let interface = Interfaces::from_code(read_u16(buf, big_endian) as u32)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let block = Self {
+            interface,
+            snap_length: read_u32(&buf[4..], big_endian),
+            options: BlockOption::options_from_bytes(&buf[INTERFACE_DESCRIPTION_BODY_LEN..], big_endian)?
+        };
+
+        if block.timestamp_units().is_none() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "if_tsresol too fine to represent"));
+        }
+
+        Ok(block)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = vec![0; INTERFACE_DESCRIPTION_BODY_LEN];
+
+        buf.splice(0..2, (self.interface.get_code() as u16).to_le_bytes());
+        buf.splice(4..8, self.snap_length.to_le_bytes());
+        buf.extend(BlockOption::options_to_bytes(&self.options));
+
+        encode_block(BlockTypes::InterfaceDescription.get_code(), &buf)
+    }
+}
@@ -0,0 +1,100 @@
+use std::io;
+use crate::pcapng::inter::block_option::{BlockOption, OPT_COMMENT};
+use crate::pcapng::inter::block_types::BlockTypes;
+use crate::pcapng::inter::utils::{encode_block, read_u32, read_u64, truncated};
+
+pub const ISB_START_TIME: u16 = 2;
+pub const ISB_END_TIME: u16 = 3;
+pub const ISB_IF_RECV: u16 = 4;
+pub const ISB_IF_DROP: u16 = 5;
+pub const ISB_FILTER_ACCEPT: u16 = 6;
+pub const ISB_OS_DROP: u16 = 7;
+pub const ISB_USR_DELIV: u16 = 8;
+
+const INTERFACE_STATISTICS_BODY_LEN: usize = 12;
+
+#[derive(Clone, Debug)]
+pub struct InterfaceStatisticsBlock {
+    interface_id: u32,
+    timestamp: u64,
+    options: Vec<BlockOption>,
+    big_endian: bool
+}
+
+impl InterfaceStatisticsBlock {
+
+    pub fn new(interface_id: u32, timestamp: u64) -> Self {
+        Self {
+            interface_id,
+            timestamp,
+            options: Vec::new(),
+            big_endian: false
+        }
+    }
+
+    pub fn get_interface_id(&self) -> u32 {
+        self.interface_id
+    }
+
+    pub fn get_timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    pub fn set_counter(&mut self, code: u16, value: u64) {
+        self.options.retain(|o| o.get_code() != code);
+        self.options.push(BlockOption::new(code, value.to_le_bytes().to_vec()));
+    }
+
+    pub fn get_counter(&self, code: u16) -> Option<u64> {
+        self.options.iter()
+            .find(|o| o.get_code() == code && o.get_value().len() == 8)
+            .map(|o| read_u64(o.get_value(), self.big_endian))
+    }
+
+    pub fn get_received(&self) -> Option<u64> {
+        self.get_counter(ISB_IF_RECV)
+    }
+
+    pub fn get_dropped(&self) -> Option<u64> {
+        self.get_counter(ISB_IF_DROP)
+    }
+
+    pub fn add_option(&mut self, option: BlockOption) {
+        self.options.push(option);
+    }
+
+    pub fn get_options(&self) -> &Vec<BlockOption> {
+        &self.options
+    }
+
+    pub fn get_comments(&self) -> Vec<String> {
+        self.options.iter().filter(|o| o.get_code() == OPT_COMMENT).map(|o| o.get_string()).collect()
+    }
+
+    pub fn from_bytes(buf: &[u8], big_endian: bool) -> io::Result<Self> {
+        if buf.len() < INTERFACE_STATISTICS_BODY_LEN {
+            return Err(truncated("interface statistics block"));
+        }
+
+        let timestamp = ((read_u32(&buf[4..], big_endian) as u64) << 32) | read_u32(&buf[8..], big_endian) as u64;
+
+        Ok(Self {
+            interface_id: read_u32(buf, big_endian),
+            timestamp,
+            options: BlockOption::options_from_bytes(&buf[INTERFACE_STATISTICS_BODY_LEN..], big_endian)?,
+            big_endian
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = vec![0; INTERFACE_STATISTICS_BODY_LEN];
+
+        buf.splice(0..4, self.interface_id.to_le_bytes());
+        buf.splice(4..8, ((self.timestamp >> 32) as u32).to_le_bytes());
+        buf.splice(8..12, (self.timestamp as u32).to_le_bytes());
+
+        buf.extend(BlockOption::options_to_bytes(&self.options));
+
+        encode_block(BlockTypes::InterfaceStatistics.get_code(), &buf)
+    }
+}
@@ -0,0 +1,6 @@
+pub mod section_header_block;
+pub mod interface_description_block;
+pub mod enhanced_packet_block;
+pub mod simple_packet_block;
+pub mod name_resolution_block;
+pub mod interface_statistics_block;
@@ -0,0 +1,126 @@
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use crate::pcapng::inter::block_option::{BlockOption, OPT_COMMENT};
+use crate::pcapng::inter::block_types::BlockTypes;
+use crate::pcapng::inter::utils::{encode_block, padded_len, read_u16, truncated};
+
+const NRB_RECORD_END: u16 = 0;
+const NRB_RECORD_IPV4: u16 = 1;
+const NRB_RECORD_IPV6: u16 = 2;
+
+#[derive(Clone, Debug)]
+pub struct NameResolutionBlock {
+    records: Vec<(IpAddr, Vec<String>)>,
+    options: Vec<BlockOption>
+}
+
+impl NameResolutionBlock {
+
+    pub fn new() -> Self {
+        Self {
+            records: Vec::new(),
+            options: Vec::new()
+        }
+    }
+
+    pub fn add_record(&mut self, address: IpAddr, names: Vec<String>) {
+        self.records.push((address, names));
+    }
+
+    pub fn get_records(&self) -> &Vec<(IpAddr, Vec<String>)> {
+        &self.records
+    }
+
+    pub fn add_option(&mut self, option: BlockOption) {
+        self.options.push(option);
+    }
+
+    pub fn get_options(&self) -> &Vec<BlockOption> {
+        &self.options
+    }
+
+    pub fn get_comments(&self) -> Vec<String> {
+        self.options.iter().filter(|o| o.get_code() == OPT_COMMENT).map(|o| o.get_string()).collect()
+    }
+
+    pub fn from_bytes(buf: &[u8], big_endian: bool) -> io::Result<Self> {
+        let mut records = Vec::new();
+        let mut off = 0;
+
+        loop {
+            if off + 4 > buf.len() {
+                return Err(truncated("name resolution block"));
+            }
+
+            let record_type = read_u16(&buf[off..], big_endian);
+            let length = read_u16(&buf[off + 2..], big_endian) as usize;
+            off += 4;
+
+            if record_type == NRB_RECORD_END {
+                break;
+            }
+
+            if off + length > buf.len() {
+                return Err(truncated("name resolution record"));
+            }
+
+            let value = &buf[off..off + length];
+            off += padded_len(length);
+
+            let (address, names) = match record_type {
+                NRB_RECORD_IPV4 if length >= 4 => {
+                    (IpAddr::V4(Ipv4Addr::new(value[0], value[1], value[2], value[3])), &value[4..])
+                }
+                NRB_RECORD_IPV6 if length >= 16 => {
+                    (IpAddr::V6(Ipv6Addr::from(<[u8; 16]>::try_from(&value[..16]).unwrap())), &value[16..])
+                }
+                _ => continue
+            };
+
+            let names = names.split(|b| *b == 0)
+                .filter(|name| !name.is_empty())
+                .map(|name| String::from_utf8_lossy(name).to_string())
+                .collect();
+
+            records.push((address, names));
+        }
+
+        Ok(Self {
+            records,
+            options: BlockOption::options_from_bytes(&buf[off.min(buf.len())..], big_endian)?
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+
+        for (address, names) in &self.records {
+            let (record_type, mut value) = match address {
+                IpAddr::V4(address) => (NRB_RECORD_IPV4, address.octets().to_vec()),
+                IpAddr::V6(address) => (NRB_RECORD_IPV6, address.octets().to_vec())
+            };
+
+            for name in names {
+                value.extend_from_slice(name.as_bytes());
+                value.push(0);
+            }
+
+            buf.extend_from_slice(&record_type.to_le_bytes());
+            buf.extend_from_slice(&(value.len() as u16).to_le_bytes());
+            buf.extend(value);
+            buf.resize(padded_len(buf.len()), 0);
+        }
+
+        buf.extend_from_slice(&[0; 4]);
+        buf.extend(BlockOption::options_to_bytes(&self.options));
+
+        encode_block(BlockTypes::NameResolution.get_code(), &buf)
+    }
+}
+
+impl Default for NameResolutionBlock {
+
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,86 @@
+use std::io;
+use crate::pcapng::inter::block_option::{BlockOption, OPT_COMMENT};
+use crate::pcapng::inter::block_types::BlockTypes;
+use crate::pcapng::inter::utils::{encode_block, read_u16, read_u64, truncated, BYTE_ORDER_MAGIC};
+
+pub const SHB_HARDWARE: u16 = 2;
+pub const SHB_OS: u16 = 3;
+pub const SHB_USER_APPLICATION: u16 = 4;
+
+const SECTION_HEADER_BODY_LEN: usize = 16;
+
+#[derive(Clone, Debug)]
+pub struct SectionHeaderBlock {
+    version_major: u16,
+    version_minor: u16,
+    section_length: i64,
+    options: Vec<BlockOption>
+}
+
+impl SectionHeaderBlock {
+
+    pub fn new() -> Self {
+        Self {
+            version_major: 1,
+            version_minor: 0,
+            section_length: -1,
+            options: Vec::new()
+        }
+    }
+
+    pub fn get_version(&self) -> (u16, u16) {
+        (self.version_major, self.version_minor)
+    }
+
+    pub fn set_section_length(&mut self, section_length: i64) {
+        self.section_length = section_length;
+    }
+
+    pub fn get_section_length(&self) -> i64 {
+        self.section_length
+    }
+
+    pub fn add_option(&mut self, option: BlockOption) {
+        self.options.push(option);
+    }
+
+    pub fn get_options(&self) -> &Vec<BlockOption> {
+        &self.options
+    }
+
+    pub fn get_comments(&self) -> Vec<String> {
+        self.options.iter().filter(|o| o.get_code() == OPT_COMMENT).map(|o| o.get_string()).collect()
+    }
+
+    pub fn from_bytes(buf: &[u8], big_endian: bool) -> io::Result<Self> {
+        if buf.len() < SECTION_HEADER_BODY_LEN {
+            return Err(truncated("section header block"));
+        }
+
+        Ok(Self {
+            version_major: read_u16(&buf[4..], big_endian),
+            version_minor: read_u16(&buf[6..], big_endian),
+            section_length: read_u64(&buf[8..], big_endian) as i64,
+            options: BlockOption::options_from_bytes(&buf[SECTION_HEADER_BODY_LEN..], big_endian)?
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = vec![0; SECTION_HEADER_BODY_LEN];
+
+        buf.splice(0..4, BYTE_ORDER_MAGIC.to_le_bytes());
+        buf.splice(4..6, self.version_major.to_le_bytes());
+        buf.splice(6..8, self.version_minor.to_le_bytes());
+        buf.splice(8..16, self.section_length.to_le_bytes());
+        buf.extend(BlockOption::options_to_bytes(&self.options));
+
+        encode_block(BlockTypes::SectionHeader.get_code(), &buf)
+    }
+}
+
+impl Default for SectionHeaderBlock {
+
+    fn default() -> Self {
+        Self::new()
+    }
+}
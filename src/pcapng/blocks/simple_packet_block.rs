@@ -0,0 +1,52 @@
+use std::io;
+use crate::pcapng::inter::block_types::BlockTypes;
+use crate::pcapng::inter::utils::{encode_block, read_u32, truncated};
+
+const SIMPLE_PACKET_BODY_LEN: usize = 4;
+
+#[derive(Clone, Debug)]
+pub struct SimplePacketBlock {
+    original_length: u32,
+    data: Vec<u8>
+}
+
+impl SimplePacketBlock {
+
+    pub fn new(original_length: u32, data: Vec<u8>) -> Self {
+        Self {
+            original_length,
+            data
+        }
+    }
+
+    pub fn get_original_length(&self) -> u32 {
+        self.original_length
+    }
+
+    pub fn get_data(&self) -> &[u8] {
+        &self.data
+    }
+
+    //CAPTURED LENGTH ISN'T STORED, IT'S THE SMALLER OF THE ORIGINAL LENGTH AND WHAT FITS IN THE BLOCK
+    pub fn from_bytes(buf: &[u8], big_endian: bool) -> io::Result<Self> {
+        if buf.len() < SIMPLE_PACKET_BODY_LEN {
+            return Err(truncated("simple packet block"));
+        }
+
+        let original_length = read_u32(buf, big_endian);
+        let captured = (original_length as usize).min(buf.len() - SIMPLE_PACKET_BODY_LEN);
+
+        Ok(Self {
+            original_length,
+            data: buf[SIMPLE_PACKET_BODY_LEN..SIMPLE_PACKET_BODY_LEN + captured].to_vec()
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(SIMPLE_PACKET_BODY_LEN + self.data.len());
+        buf.extend_from_slice(&self.original_length.to_le_bytes());
+        buf.extend_from_slice(&self.data);
+
+        encode_block(BlockTypes::SimplePacket.get_code(), &buf)
+    }
+}
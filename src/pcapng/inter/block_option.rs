@@ -0,0 +1,79 @@
+use std::io;
+use crate::pcapng::inter::utils::{padded_len, read_u16, truncated};
+
+pub const OPT_END_OF_OPT: u16 = 0;
+pub const OPT_COMMENT: u16 = 1;
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct BlockOption {
+    code: u16,
+    value: Vec<u8>
+}
+
+impl BlockOption {
+
+    pub fn new(code: u16, value: Vec<u8>) -> Self {
+        Self {
+            code,
+            value
+        }
+    }
+
+    pub fn comment(comment: &str) -> Self {
+        Self::new(OPT_COMMENT, comment.as_bytes().to_vec())
+    }
+
+    pub fn get_code(&self) -> u16 {
+        self.code
+    }
+
+    pub fn get_value(&self) -> &[u8] {
+        &self.value
+    }
+
+    pub fn get_string(&self) -> String {
+        String::from_utf8_lossy(&self.value).trim_end_matches('\0').to_string()
+    }
+
+    pub fn options_from_bytes(buf: &[u8], big_endian: bool) -> io::Result<Vec<Self>> {
+        let mut options = Vec::new();
+        let mut off = 0;
+
+        while off + 4 <= buf.len() {
+            let code = read_u16(&buf[off..], big_endian);
+            let length = read_u16(&buf[off + 2..], big_endian) as usize;
+            off += 4;
+
+            if code == OPT_END_OF_OPT {
+                break;
+            }
+
+            if off + length > buf.len() {
+                return Err(truncated("option"));
+            }
+
+            options.push(Self::new(code, buf[off..off + length].to_vec()));
+            off += padded_len(length);
+        }
+
+        Ok(options)
+    }
+
+    pub fn options_to_bytes(options: &[Self]) -> Vec<u8> {
+        if options.is_empty() {
+            return Vec::new();
+        }
+
+        let mut buf = Vec::new();
+
+        for option in options {
+            buf.extend_from_slice(&option.code.to_le_bytes());
+            buf.extend_from_slice(&(option.value.len() as u16).to_le_bytes());
+            buf.extend_from_slice(&option.value);
+            buf.resize(padded_len(buf.len()), 0);
+        }
+
+        buf.extend_from_slice(&[0; 4]);
+        buf
+    }
+}
@@ -0,0 +1,45 @@
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum BlockTypes {
+    SectionHeader,
+    InterfaceDescription,
+    SimplePacket,
+    NameResolution,
+    InterfaceStatistics,
+    EnhancedPacket
+}
+
+impl BlockTypes {
+
+    pub fn from_code(code: u32) -> Result<Self, String> {
+        for c in [Self::SectionHeader, Self::InterfaceDescription, Self::SimplePacket, Self::NameResolution, Self::InterfaceStatistics, Self::EnhancedPacket] {
+            if c.get_code() == code {
+                return Ok(c);
+            }
+        }
+
+        Err(format!("Couldn't find for code: {}", code))
+    }
+
+    pub fn get_code(&self) -> u32 {
+        match self {
+            Self::SectionHeader => 0x0A0D0D0A,
+            Self::InterfaceDescription => 0x00000001,
+            Self::SimplePacket => 0x00000003,
+            Self::NameResolution => 0x00000004,
+            Self::InterfaceStatistics => 0x00000005,
+            Self::EnhancedPacket => 0x00000006
+        }
+    }
+
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        match self {
+            Self::SectionHeader => "Section Header Block",
+            Self::InterfaceDescription => "Interface Description Block",
+            Self::SimplePacket => "Simple Packet Block",
+            Self::NameResolution => "Name Resolution Block",
+            Self::InterfaceStatistics => "Interface Statistics Block",
+            Self::EnhancedPacket => "Enhanced Packet Block"
+        }.to_string()
+    }
+}
@@ -0,0 +1,33 @@
+use crate::pcapng::blocks::enhanced_packet_block::EnhancedPacketBlock;
+use crate::pcapng::blocks::interface_description_block::InterfaceDescriptionBlock;
+use crate::pcapng::blocks::interface_statistics_block::InterfaceStatisticsBlock;
+use crate::pcapng::blocks::name_resolution_block::NameResolutionBlock;
+use crate::pcapng::blocks::section_header_block::SectionHeaderBlock;
+use crate::pcapng::blocks::simple_packet_block::SimplePacketBlock;
+use crate::pcapng::inter::utils::encode_block;
+
+#[derive(Clone, Debug)]
+pub enum Blocks {
+    SectionHeader(SectionHeaderBlock),
+    InterfaceDescription(InterfaceDescriptionBlock),
+    SimplePacket(SimplePacketBlock),
+    NameResolution(NameResolutionBlock),
+    InterfaceStatistics(InterfaceStatisticsBlock),
+    EnhancedPacket(EnhancedPacketBlock),
+    Unknown(u32, Vec<u8>)
+}
+
+impl Blocks {
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::SectionHeader(block) => block.to_bytes(),
+            Self::InterfaceDescription(block) => block.to_bytes(),
+            Self::SimplePacket(block) => block.to_bytes(),
+            Self::NameResolution(block) => block.to_bytes(),
+            Self::InterfaceStatistics(block) => block.to_bytes(),
+            Self::EnhancedPacket(block) => block.to_bytes(),
+            Self::Unknown(block_type, body) => encode_block(*block_type, body)
+        }
+    }
+}
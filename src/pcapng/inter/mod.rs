@@ -0,0 +1,4 @@
+pub mod block_types;
+pub mod block_option;
+pub mod blocks;
+pub mod utils;
@@ -0,0 +1,52 @@
+use std::io;
+
+pub const BYTE_ORDER_MAGIC: u32 = 0x1A2B3C4D;
+
+pub fn read_u16(buf: &[u8], big_endian: bool) -> u16 {
+    let buf = [buf[0], buf[1]];
+
+    match big_endian {
+        true => u16::from_be_bytes(buf),
+        false => u16::from_le_bytes(buf)
+    }
+}
+
+pub fn read_u32(buf: &[u8], big_endian: bool) -> u32 {
+    let buf = [buf[0], buf[1], buf[2], buf[3]];
+
+    match big_endian {
+        true => u32::from_be_bytes(buf),
+        false => u32::from_le_bytes(buf)
+    }
+}
+
+pub fn read_u64(buf: &[u8], big_endian: bool) -> u64 {
+    let buf = [buf[0], buf[1], buf[2], buf[3], buf[4], buf[5], buf[6], buf[7]];
+
+    match big_endian {
+        true => u64::from_be_bytes(buf),
+        false => u64::from_le_bytes(buf)
+    }
+}
+
+pub fn padded_len(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+//WRAPS A BLOCK BODY WITH THE TYPE AND BOTH LENGTH FIELDS, ALWAYS LITTLE ENDIAN
+pub fn encode_block(block_type: u32, body: &[u8]) -> Vec<u8> {
+    let total_length = (12 + padded_len(body.len())) as u32;
+
+    let mut buf = Vec::with_capacity(total_length as usize);
+    buf.extend_from_slice(&block_type.to_le_bytes());
+    buf.extend_from_slice(&total_length.to_le_bytes());
+    buf.extend_from_slice(body);
+    buf.resize(total_length as usize - 4, 0);
+    buf.extend_from_slice(&total_length.to_le_bytes());
+
+    buf
+}
+
+pub fn truncated(block: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("Truncated {}", block))
+}
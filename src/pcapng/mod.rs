@@ -0,0 +1,4 @@
+pub mod inter;
+pub mod blocks;
+pub mod pcapng_reader;
+pub mod pcapng_writer;
@@ -0,0 +1,232 @@
+use std::io;
+use std::io::Read;
+use crate::bpf::bpf_program::BpfProgram;
+use crate::bpf::inter::filter_error::FilterError;
+use crate::packet::packet::Packet;
+use crate::pcapng::blocks::enhanced_packet_block::EnhancedPacketBlock;
+use crate::pcapng::blocks::interface_description_block::InterfaceDescriptionBlock;
+use crate::pcapng::blocks::interface_statistics_block::InterfaceStatisticsBlock;
+use crate::pcapng::blocks::name_resolution_block::NameResolutionBlock;
+use crate::pcapng::blocks::section_header_block::SectionHeaderBlock;
+use crate::pcapng::blocks::simple_packet_block::SimplePacketBlock;
+use crate::pcapng::inter::block_types::BlockTypes;
+use crate::pcapng::inter::blocks::Blocks;
+use crate::pcapng::inter::utils::{read_u32, BYTE_ORDER_MAGIC};
+
+const MAX_BLOCK_LEN: usize = 64 * 1024 * 1024;
+
+#[derive(Debug)]
+pub struct PcapNgReader<R: Read> {
+    reader: R,
+    big_endian: bool,
+    section: SectionHeaderBlock,
+    interfaces: Vec<InterfaceDescriptionBlock>,
+    filter: Option<BpfProgram>
+}
+
+impl<R: Read> PcapNgReader<R> {
+
+    pub fn new(reader: R) -> io::Result<Self> {
+        let mut _self = Self {
+            reader,
+            big_endian: false,
+            section: SectionHeaderBlock::new(),
+            interfaces: Vec::new(),
+            filter: None
+        };
+
+        match _self.next_block()? {
+            Some(Blocks::SectionHeader(_)) => Ok(_self),
+            _ => Err(io::Error::new(io::ErrorKind::InvalidData, "File doesn't start with a section header block"))
+        }
+    }
+
+    pub fn is_big_endian(&self) -> bool {
+        self.big_endian
+    }
+
+    pub fn get_section(&self) -> &SectionHeaderBlock {
+        &self.section
+    }
+
+    pub fn get_interfaces(&self) -> &Vec<InterfaceDescriptionBlock> {
+        &self.interfaces
+    }
+
+    //ONLY APPLIES TO PACKETS, NEXT BLOCK STILL RETURNS EVERY BLOCK
+    pub fn set_filter(&mut self, expression: &str) -> Result<(), FilterError> {
+        self.set_program(BpfProgram::compile(expression)?)
+    }
+
+    pub fn set_program(&mut self, program: BpfProgram) -> Result<(), FilterError> {
+        program.verify()?;
+        self.filter = Some(program);
+        Ok(())
+    }
+
+    pub fn clear_filter(&mut self) {
+        self.filter = None;
+    }
+
+    pub fn get_filter(&self) -> Option<&BpfProgram> {
+        self.filter.as_ref()
+    }
+
+    //RETURNS THE INTERFACE ID AND PACKET, SKIPPING ANY NON PACKET BLOCKS
+    pub fn next_packet(&mut self) -> io::Result<(u32, Packet)> {
+        self.read_packet()?.ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "No more packets"))
+    }
+
+    fn read_packet(&mut self) -> io::Result<Option<(u32, Packet)>> {
+        loop {
+            match self.next_block()? {
+                Some(Blocks::EnhancedPacket(block)) => {
+                    let data = match self.apply_filter(block.get_data(), block.get_original_length()) {
+                        Some(data) => data,
+                        None => continue
+                    };
+
+                    let interface = self.get_interface(block.get_interface_id())?;
+                    let frame_time = block.get_timestamp() as u128 * 1_000_000_000 / interface.get_timestamp_units();
+                    let packet = Packet::with_original_length(interface.get_interface(), frame_time, data, block.get_original_length() as usize)?;
+                    return Ok(Some((block.get_interface_id(), packet)));
+                }
+                Some(Blocks::SimplePacket(block)) => {
+                    let data = match self.apply_filter(block.get_data(), block.get_original_length()) {
+                        Some(data) => data,
+                        None => continue
+                    };
+
+                    let interface = self.get_interface(0)?;
+                    return Ok(Some((0, Packet::with_original_length(interface.get_interface(), 0, data, block.get_original_length() as usize)?)));
+                }
+                Some(_) => {}
+                None => return Ok(None)
+            }
+        }
+    }
+
+    pub fn next_block(&mut self) -> io::Result<Option<Blocks>> {
+        let mut header = [0u8; 8];
+        match self.read_header(&mut header)? {
+            0 => return Ok(None),
+            8 => {}
+            _ => return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "Truncated block header"))
+        }
+
+        let mut magic = [0u8; 4];
+        let block_type = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+
+        //THE SECTION HEADER TYPE IS A PALINDROME, ITS BYTE ORDER MAGIC DECIDES THE ENDIANNESS OF THE SECTION
+        if block_type == BlockTypes::SectionHeader.get_code() {
+            self.reader.read_exact(&mut magic)?;
+
+            self.big_endian = match u32::from_le_bytes(magic) {
+                BYTE_ORDER_MAGIC => false,
+                m if m.swap_bytes() == BYTE_ORDER_MAGIC => true,
+                _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid byte order magic"))
+            };
+        }
+
+        let block_type = read_u32(&header, self.big_endian);
+        let total_length = read_u32(&header[4..], self.big_endian) as usize;
+
+        if total_length < 12 || !total_length.is_multiple_of(4) || total_length > MAX_BLOCK_LEN {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Invalid block length: {}", total_length)));
+        }
+
+        let mut body = vec![0u8; total_length - 8];
+        match BlockTypes::from_code(block_type) {
+            Ok(BlockTypes::SectionHeader) => {
+                if total_length < 28 {
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, "Truncated section header block"));
+                }
+
+                body[..4].copy_from_slice(&magic);
+                self.reader.read_exact(&mut body[4..])?;
+            }
+            _ => {
+                self.reader.read_exact(&mut body)?;
+            }
+        }
+
+        if read_u32(&body[body.len() - 4..], self.big_endian) as usize != total_length {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Block lengths don't match"));
+        }
+
+        let body = &body[..body.len() - 4];
+
+        let block = match BlockTypes::from_code(block_type) {
+            Ok(BlockTypes::SectionHeader) => {
+                let section = SectionHeaderBlock::from_bytes(body, self.big_endian)?;
+                self.section = section.clone();
+                self.interfaces.clear();
+                Blocks::SectionHeader(section)
+            }
+            Ok(BlockTypes::InterfaceDescription) => {
+                let interface = InterfaceDescriptionBlock::from_bytes(body, self.big_endian)?;
+                self.interfaces.push(interface.clone());
+                Blocks::InterfaceDescription(interface)
+            }
+            Ok(BlockTypes::SimplePacket) => {
+                Blocks::SimplePacket(SimplePacketBlock::from_bytes(body, self.big_endian)?)
+            }
+            Ok(BlockTypes::NameResolution) => {
+                Blocks::NameResolution(NameResolutionBlock::from_bytes(body, self.big_endian)?)
+            }
+            Ok(BlockTypes::InterfaceStatistics) => {
+                Blocks::InterfaceStatistics(InterfaceStatisticsBlock::from_bytes(body, self.big_endian)?)
+            }
+            Ok(BlockTypes::EnhancedPacket) => {
+                Blocks::EnhancedPacket(EnhancedPacketBlock::from_bytes(body, self.big_endian)?)
+            }
+            Err(_) => {
+                Blocks::Unknown(block_type, body.to_vec())
+            }
+        };
+
+        Ok(Some(block))
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn apply_filter<'a>(&self, data: &'a [u8], original_length: u32) -> Option<&'a [u8]> {
+        match &self.filter {
+            Some(filter) => match filter.run(data, original_length) as usize {
+                0 => None,
+                keep => Some(&data[..keep.min(data.len())])
+            },
+            None => Some(data)
+        }
+    }
+
+    fn get_interface(&self, interface_id: u32) -> io::Result<&InterfaceDescriptionBlock> {
+        self.interfaces.get(interface_id as usize)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("Unknown interface id: {}", interface_id)))
+    }
+
+    fn read_header(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut read = 0;
+        while read < buf.len() {
+            match self.reader.read(&mut buf[read..]) {
+                Ok(0) => break,
+                Ok(n) => read += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e)
+            }
+        }
+
+        Ok(read)
+    }
+}
+
+impl<R: Read> Iterator for PcapNgReader<R> {
+
+    type Item = io::Result<(u32, Packet)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_packet().transpose()
+    }
+}
@@ -0,0 +1,160 @@
+use std::io;
+use std::io::Write;
+use crate::devices::Device;
+use crate::packet::packet::Packet;
+use crate::pcapng::blocks::enhanced_packet_block::EnhancedPacketBlock;
+use crate::pcapng::blocks::interface_description_block::{InterfaceDescriptionBlock, IF_TSRESOL};
+use crate::pcapng::blocks::interface_statistics_block::InterfaceStatisticsBlock;
+use crate::pcapng::blocks::name_resolution_block::NameResolutionBlock;
+use crate::pcapng::blocks::section_header_block::SectionHeaderBlock;
+use crate::pcapng::blocks::simple_packet_block::SimplePacketBlock;
+use crate::pcapng::inter::block_option::BlockOption;
+use crate::pcapng::inter::blocks::Blocks;
+
+pub const PCAPNG_DEFAULT_SNAP_LENGTH: u32 = 262144;
+
+//TIMESTAMPS ARE WRITTEN IN MICROSECONDS
+const TIMESTAMP_RESOLUTION: u8 = 6;
+
+#[derive(Debug)]
+pub struct PcapNgWriter<W: Write> {
+    writer: W,
+    interfaces: Vec<InterfaceDescriptionBlock>
+}
+
+impl<W: Write> PcapNgWriter<W> {
+
+    pub fn new(writer: W) -> io::Result<Self> {
+        Self::with_section(writer, SectionHeaderBlock::new())
+    }
+
+    pub fn with_section(mut writer: W, section: SectionHeaderBlock) -> io::Result<Self> {
+        writer.write_all(&section.to_bytes())?;
+
+        Ok(Self {
+            writer,
+            interfaces: Vec::new()
+        })
+    }
+
+    //RETURNS THE INTERFACE ID TO USE WHEN WRITING PACKETS FROM THIS DEVICE
+    pub fn add_device(&mut self, device: &Device) -> io::Result<u32> {
+        self.add_interface(InterfaceDescriptionBlock::from_device(device, PCAPNG_DEFAULT_SNAP_LENGTH))
+    }
+
+    pub fn add_interface(&mut self, mut interface: InterfaceDescriptionBlock) -> io::Result<u32> {
+        if interface.get_options().iter().all(|o| o.get_code() != IF_TSRESOL) {
+            interface.add_option(BlockOption::new(IF_TSRESOL, vec![TIMESTAMP_RESOLUTION]));
+        }
+
+        Self::check_resolution(&interface)?;
+
+        self.writer.write_all(&interface.to_bytes())?;
+        self.interfaces.push(interface);
+        Ok(self.interfaces.len() as u32 - 1)
+    }
+
+    pub fn get_interfaces(&self) -> &Vec<InterfaceDescriptionBlock> {
+        &self.interfaces
+    }
+
+    pub fn write_packet(&mut self, interface_id: u32, packet: &Packet) -> io::Result<()> {
+        self.write_packet_with_options(interface_id, packet, Vec::new())
+    }
+
+    pub fn write_packet_with_comment(&mut self, interface_id: u32, packet: &Packet, comment: &str) -> io::Result<()> {
+        self.write_packet_with_options(interface_id, packet, vec![BlockOption::comment(comment)])
+    }
+
+    pub fn write_packet_with_options(&mut self, interface_id: u32, packet: &Packet, options: Vec<BlockOption>) -> io::Result<()> {
+        let interface = self.get_interface(interface_id)?;
+        Self::check_link_type(interface, packet)?;
+
+        let data = packet.to_bytes();
+        let captured = Self::captured_length(interface, data.len());
+        let timestamp = packet.get_frame_time().checked_mul(interface.get_timestamp_units())
+            .map(|t| t / 1_000_000_000)
+            .filter(|t| *t <= u64::MAX as u128)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Frame time doesn't fit the interface timestamp resolution"))?;
+
+        let original_length = packet.get_original_length().max(data.len());
+        let mut block = EnhancedPacketBlock::new(interface_id, timestamp as u64, original_length as u32, data[..captured].to_vec());
+        for option in options {
+            block.add_option(option);
+        }
+
+        self.writer.write_all(&block.to_bytes())
+    }
+
+    //SIMPLE PACKETS ALWAYS BELONG TO THE FIRST INTERFACE AND CARRY NO TIMESTAMP
+    pub fn write_simple_packet(&mut self, packet: &Packet) -> io::Result<()> {
+        let interface = self.get_interface(0)?;
+        Self::check_link_type(interface, packet)?;
+
+        let data = packet.to_bytes();
+        let captured = Self::captured_length(interface, data.len());
+
+        let original_length = packet.get_original_length().max(data.len());
+        self.writer.write_all(&SimplePacketBlock::new(original_length as u32, data[..captured].to_vec()).to_bytes())
+    }
+
+    pub fn write_name_resolution(&mut self, block: &NameResolutionBlock) -> io::Result<()> {
+        self.writer.write_all(&block.to_bytes())
+    }
+
+    pub fn write_statistics(&mut self, block: &InterfaceStatisticsBlock) -> io::Result<()> {
+        self.get_interface(block.get_interface_id())?;
+        self.writer.write_all(&block.to_bytes())
+    }
+
+    pub fn write_block(&mut self, block: &Blocks) -> io::Result<()> {
+        match block {
+            Blocks::SectionHeader(_) => {
+                self.interfaces.clear();
+            }
+            Blocks::InterfaceDescription(interface) => {
+                Self::check_resolution(interface)?;
+                self.interfaces.push(interface.clone());
+            }
+            _ => {}
+        }
+
+        self.writer.write_all(&block.to_bytes())
+    }
+
+    fn check_link_type(interface: &InterfaceDescriptionBlock, packet: &Packet) -> io::Result<()> {
+        if *packet.get_interface() != interface.get_interface() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Packet interface doesn't match the interface link type"));
+        }
+
+        Ok(())
+    }
+
+    //PACKETS ARE TIMESTAMPED IN THE INTERFACE'S UNITS, A RESOLUTION THEY CAN'T BE COUNTED IN WOULD GIVE READERS WRONG TIMES
+    fn check_resolution(interface: &InterfaceDescriptionBlock) -> io::Result<()> {
+        match interface.timestamp_units() {
+            Some(_) => Ok(()),
+            None => Err(io::Error::new(io::ErrorKind::InvalidInput, "if_tsresol too fine to represent"))
+        }
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn get_interface(&self, interface_id: u32) -> io::Result<&InterfaceDescriptionBlock> {
+        self.interfaces.get(interface_id as usize)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, format!("Unknown interface id: {}", interface_id)))
+    }
+
+    fn captured_length(interface: &InterfaceDescriptionBlock, length: usize) -> usize {
+        match interface.get_snap_length() {
+            0 => length,
+            snap_length => length.min(snap_length as usize)
+        }
+    }
+}
@@ -0,0 +1,192 @@
+mod common;
+
+use pcap::bpf::bpf_program::BpfProgram;
+use pcap::bpf::inter::bpf_instruction::{BpfInstruction, BPF_ABS, BPF_ALU, BPF_DIV, BPF_H, BPF_JA, BPF_JEQ, BPF_JMP, BPF_K, BPF_LD, BPF_MEM, BPF_RET, BPF_ST};
+use pcap::bpf::inter::filter_error::FilterError;
+use common::load;
+
+/*
+Compiled filters are run in the userspace machine against the round trip corpus,
+so every expression is checked against frames it should and shouldn't match.
+*/
+
+fn matching(expression: &str) -> Vec<String> {
+    let program = BpfProgram::compile(expression).unwrap();
+    program.verify().unwrap();
+    common::names().into_iter().filter(|name| program.matches(&load(name))).collect()
+}
+
+fn check_jumps(program: &BpfProgram) {
+    let instructions = program.get_instructions();
+
+    for (i, instruction) in instructions.iter().enumerate() {
+        if instruction.get_class() == BPF_JMP {
+            match instruction.get_code() & 0xf0 {
+                BPF_JA => assert!(i + 1 + (instruction.get_k() as usize) < instructions.len()),
+                _ => {
+                    assert!(i + 1 + (instruction.get_jt() as usize) < instructions.len());
+                    assert!(i + 1 + (instruction.get_jf() as usize) < instructions.len());
+                }
+            }
+        }
+    }
+
+    assert_eq!(instructions.last().unwrap().get_class(), BPF_RET);
+}
+
+#[test]
+fn compile() {
+    assert_eq!(BpfProgram::compile("ip").unwrap().get_instructions(), &vec![
+        BpfInstruction::statement(BPF_LD | BPF_H | BPF_ABS, 12),
+        BpfInstruction::jump(BPF_JMP | BPF_JEQ | BPF_K, 0x800, 0, 1),
+        BpfInstruction::statement(BPF_RET | BPF_K, 262144),
+        BpfInstruction::statement(BPF_RET | BPF_K, 0)
+    ]);
+
+    assert_eq!(BpfProgram::compile("").unwrap().len(), 2);
+    assert_eq!(BpfProgram::compile("port 80 or 443").unwrap(), BpfProgram::compile("port 80 or port 443").unwrap());
+    assert_eq!(BpfProgram::compile("not not tcp").unwrap(), BpfProgram::compile("tcp").unwrap());
+
+    for expression in [
+        "tcp port 443 and host 10.0.0.1",
+        "src net 192.168.0.0/16 and not dst port 22",
+        "ip6 host fe80::1 or ether src 00:11:22:33:44:55",
+        "net 2001:db8::/32",
+        "ip proto \\udp && udp[2:2] = 53",
+        "tcp[tcpflags] & tcp-syn != 0",
+        "icmp[icmptype] == icmp-echo or icmp6",
+        "ether proto 0x88cc or arp or len >= 1000",
+        "(ip or ip6) and !(udp or tcp)",
+        "vlan 100 and udp port 53 or vlan and vlan 20"
+    ] {
+        let program = BpfProgram::compile(expression).unwrap();
+        program.verify().unwrap();
+        check_jumps(&program);
+    }
+}
+
+#[test]
+fn long_jumps() {
+    //PUSHES THE ACCEPT FAR PAST WHAT A CONDITIONAL JUMP CAN REACH
+    let expression = (0..100).map(|i| format!("host 10.0.{}.1", i)).collect::<Vec<_>>().join(" or ");
+    let program = BpfProgram::compile(&expression).unwrap();
+    assert!(program.len() > 255);
+    assert!(program.get_instructions().iter().any(|instruction| instruction.get_code() == BPF_JMP | BPF_JA));
+    check_jumps(&program);
+}
+
+#[test]
+fn errors() {
+    assert_eq!(BpfProgram::compile("tcp port").unwrap_err(), FilterError::UnexpectedEnd);
+    assert_eq!(BpfProgram::compile("host 10.0.0.300").unwrap_err(), FilterError::InvalidValue {
+        position: 5,
+        value: "10.0.0.300".to_string()
+    });
+    assert_eq!(BpfProgram::compile("icmp port 80").unwrap_err().get_position(), Some(10));
+    assert!(matches!(BpfProgram::compile("(tcp").unwrap_err(), FilterError::UnexpectedEnd));
+    assert!(matches!(BpfProgram::compile("tcp )").unwrap_err(), FilterError::UnexpectedToken { position: 4, .. }));
+    assert!(matches!(BpfProgram::compile("ip[0:3] = 1").unwrap_err(), FilterError::InvalidValue { .. }));
+    assert!(matches!(BpfProgram::compile("80").unwrap_err(), FilterError::UnexpectedToken { .. }));
+    assert!(matches!(BpfProgram::compile("host 10.0.0.1 $").unwrap_err(), FilterError::UnexpectedToken { position: 14, .. }));
+    assert_eq!(BpfProgram::compile("vlan 0x1000").unwrap_err(), FilterError::InvalidValue {
+        position: 5,
+        value: "0x1000".to_string()
+    });
+}
+
+#[test]
+fn filters() {
+    assert_eq!(matching("tcp port 443"), vec!["ipv4_tcp_ack_padded", "ipv4_tcp_syn_options", "ipv6_tcp"]);
+    assert_eq!(matching("tcp port 443 and host 192.168.0.1"), vec!["ipv4_tcp_ack_padded", "ipv4_tcp_syn_options"]);
+    assert_eq!(matching("udp"), vec!["ipv4_udp_dhcp_discover", "ipv4_udp_dns", "ipv4_udp_fragment", "ipv6_udp"]);
+    assert_eq!(matching("udp dst port 53 or 67"), vec!["ipv4_udp_dhcp_discover", "ipv4_udp_dns"]);
+    assert_eq!(matching("port 515"), Vec::<&str>::new());
+    assert_eq!(matching("host 192.168.0.2 and not ip"), vec!["arp_reply", "arp_request"]);
+    assert_eq!(matching("arp dst host 192.168.0.1"), vec!["arp_request"]);
+    assert_eq!(matching("net 192.168.0.0/24 and icmp"), vec!["ipv4_icmp_echo", "ipv4_icmp_short_padded"]);
+    assert_eq!(matching("src net 0.0.0.0 mask 255.0.0.0"), vec!["ipv4_udp_dhcp_discover"]);
+    assert_eq!(matching("ip6 dst host fe80::6477:88ff:fe99:aabb and (tcp or icmp6)"), vec!["ipv6_icmpv6_echo", "ipv6_tcp"]);
+    assert_eq!(matching("ip6 net fe80::/10 and proto 0"), vec!["ipv6_hop_by_hop"]);
+    assert_eq!(matching("ether src 66:77:88:99:aa:bb"), vec!["arp_reply", "ipv4_icmp_short_padded", "ipv6_tcp", "qinq_ipv4_icmp_padded"]);
+    assert_eq!(matching("ether dst ff:ff:ff:ff:ff:ff"), vec!["arp_request", "ipv4_udp_dhcp_discover"]);
+    assert_eq!(matching("ether[0] & 1 != 0 and not ether dst ff:ff:ff:ff:ff:ff"), vec!["ipv6_hop_by_hop", "lldp"]);
+    assert_eq!(matching("ether proto 0x88cc or ip proto 47"), vec!["ipv4_gre", "lldp"]);
+    assert_eq!(matching("tcp[tcpflags] & tcp-syn != 0"), vec!["ipv4_tcp_syn_options"]);
+    assert_eq!(matching("icmp[icmptype] = icmp-echo"), vec!["ipv4_icmp_echo"]);
+    assert_eq!(matching("ip[6:2] & 0x1fff > 0"), vec!["ipv4_udp_fragment"]);
+    assert_eq!(matching("len > 100"), vec!["ipv4_udp_dhcp_discover"]);
+    assert_eq!(matching("len <= 60 and ip"), vec!["ipv4_icmp_short_padded", "ipv4_tcp_ack_padded"]);
+    assert_eq!(matching(""), common::names());
+}
+
+#[test]
+fn vlans() {
+    assert_eq!(matching("vlan"), vec!["qinq_ipv4_icmp_padded", "vlan_ipv4_udp_dns"]);
+    assert_eq!(matching("vlan 100"), vec!["vlan_ipv4_udp_dns"]);
+    assert_eq!(matching("vlan 10"), vec!["qinq_ipv4_icmp_padded"]);
+    assert_eq!(matching("vlan 20"), Vec::<&str>::new());
+    assert_eq!(matching("vlan and vlan 20 and icmp"), vec!["qinq_ipv4_icmp_padded"]);
+    assert_eq!(matching("vlan 100 and udp dst port 53"), vec!["vlan_ipv4_udp_dns"]);
+    assert_eq!(matching("vlan and host 192.168.0.1"), vec!["vlan_ipv4_udp_dns"]);
+    assert_eq!(matching("vlan and vlan and host 192.168.0.1"), vec!["qinq_ipv4_icmp_padded"]);
+
+    //WITHOUT "vlan" THE TAGGED FRAMES DON'T LOOK LIKE IP AT ALL
+    assert_eq!(matching("udp dst port 53"), vec!["ipv4_udp_dns"]);
+}
+
+#[test]
+fn machine() {
+    let program = BpfProgram::compile_with_snap_length("tcp", 64).unwrap();
+    assert_eq!(program.run(&load("ipv4_tcp_syn_options"), 74), 64);
+    assert_eq!(program.run(&load("ipv4_udp_dns"), 71), 0);
+
+    //LOADS PAST THE END OF THE PACKET REJECT IT
+    assert_eq!(program.run(&load("ipv4_tcp_syn_options")[..20], 74), 0);
+
+    let program = BpfProgram::new(vec![
+        BpfInstruction::statement(BPF_LD | BPF_H | BPF_ABS, 12),
+        BpfInstruction::statement(BPF_ST, 3),
+        BpfInstruction::statement(BPF_LD | BPF_MEM, 3),
+        BpfInstruction::statement(BPF_RET | 0x10, 0)
+    ]);
+    program.verify().unwrap();
+    assert_eq!(program.run(&load("lldp"), 60), 0x88cc);
+}
+
+#[test]
+fn verifier() {
+    let ret = BpfInstruction::statement(BPF_RET | BPF_K, 0);
+
+    assert_eq!(BpfProgram::new(vec![]).verify().unwrap_err(), FilterError::Empty);
+    assert_eq!(BpfProgram::new(vec![BpfInstruction::statement(BPF_LD | BPF_H | BPF_ABS, 12)]).verify().unwrap_err(), FilterError::MissingReturn);
+    assert_eq!(BpfProgram::new(vec![BpfInstruction::jump(BPF_JMP | BPF_JEQ | BPF_K, 0, 0, 1), ret]).verify().unwrap_err(), FilterError::BadJump {
+        index: 0
+    });
+    assert_eq!(BpfProgram::new(vec![BpfInstruction::statement(BPF_JMP | BPF_JA, 5), ret]).verify().unwrap_err().get_index(), Some(0));
+    assert_eq!(BpfProgram::new(vec![ret, BpfInstruction::statement(BPF_ST, 16), ret]).verify().unwrap_err(), FilterError::BadMemory {
+        index: 1
+    });
+    assert_eq!(BpfProgram::new(vec![BpfInstruction::statement(BPF_ALU | BPF_DIV | BPF_K, 0), ret]).verify().unwrap_err(), FilterError::DivisionByZero {
+        index: 0
+    });
+    assert_eq!(BpfProgram::new(vec![BpfInstruction::statement(0xffff, 0), ret]).verify().unwrap_err(), FilterError::InvalidInstruction {
+        index: 0,
+        code: 0xffff
+    });
+}
+
+#[test]
+fn disassemble() {
+    assert_eq!(BpfProgram::compile("tcp dst port 443").unwrap().to_string().lines().take(9).collect::<Vec<_>>(), vec![
+        "(000) ldh      [12]",
+        "(001) jeq      #0x800           jt 2\tjf 9",
+        "(002) ldb      [23]",
+        "(003) jeq      #0x6             jt 4\tjf 9",
+        "(004) ldh      [20]",
+        "(005) jset     #0x1fff          jt 9\tjf 6",
+        "(006) ldxb     4*([14]&0xf)",
+        "(007) ldh      [x + 16]",
+        "(008) jeq      #0x1bb           jt 15\tjf 9"
+    ]);
+}
+
@@ -0,0 +1,33 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/*
+Corpus files are hex, whitespace separated, with # comment lines.
+*/
+
+pub fn load(name: &str) -> Vec<u8> {
+    parse(&fs::read_to_string(corpus().join(format!("{}.hex", name))).unwrap())
+}
+
+fn parse(hex: &str) -> Vec<u8> {
+    hex.lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .flat_map(|line| line.split_whitespace())
+        .map(|byte| u8::from_str_radix(byte, 16).unwrap())
+        .collect()
+}
+
+//EVERY FRAME IN tests/corpus BY NAME, SORTED SO RESULTS ARE STABLE
+pub fn names() -> Vec<String> {
+    let mut names = fs::read_dir(corpus()).unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "hex"))
+        .map(|path| path.file_stem().unwrap().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+fn corpus() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/corpus")
+}
@@ -0,0 +1,313 @@
+mod common;
+
+use std::net::IpAddr;
+use pcap::packet::inter::decode_error::DecodeError;
+use pcap::packet::inter::interfaces::Interfaces;
+use pcap::packet::layers::ethernet_frame::arp::arp_extension::ArpExtension;
+use pcap::packet::layers::ethernet_frame::arp::inter::arp_operations::ArpOperations;
+use pcap::packet::layers::ethernet_frame::ethernet_frame::EthernetFrame;
+use pcap::packet::layers::ethernet_frame::ethernet_view::EthernetView;
+use pcap::packet::layers::ethernet_frame::inter::types::Types;
+use pcap::packet::layers::ethernet_frame::ip::icmp::icmp_layer::IcmpLayer;
+use pcap::packet::layers::ethernet_frame::ip::icmpv6::icmpv6_layer::Icmpv6Layer;
+use pcap::packet::layers::ethernet_frame::ip::ipv4_layer::Ipv4Layer;
+use pcap::packet::layers::ethernet_frame::ip::ipv4_view::Ipv4View;
+use pcap::packet::layers::ethernet_frame::ip::ipv6_layer::Ipv6Layer;
+use pcap::packet::layers::ethernet_frame::ip::ipv6_view::Ipv6View;
+use pcap::packet::layers::ethernet_frame::ip::tcp::tcp_layer::TcpLayer;
+use pcap::packet::layers::ethernet_frame::ip::tcp::tcp_view::TcpView;
+use pcap::packet::layers::ethernet_frame::ip::udp::dhcp::dhcp_layer::DhcpLayer;
+use pcap::packet::layers::ethernet_frame::ip::udp::inter::udp_types::UdpTypes;
+use pcap::packet::layers::ethernet_frame::ip::udp::udp_layer::UdpLayer;
+use pcap::packet::layers::ethernet_frame::ip::udp::udp_view::UdpView;
+use pcap::packet::layers::ethernet_frame::vlan::vlan_layer::VlanLayer;
+use pcap::packet::layers::inter::layer::Layer;
+use pcap::packet::layers::raw::raw_layer::RawLayer;
+use pcap::packet::packet::{decode_packet_checked, Packet};
+use pcap::pcap::inter::timestamp_precisions::TimestampPrecisions;
+use pcap::pcap::pcap_reader::PcapReader;
+use pcap::pcap::pcap_writer::PcapWriter;
+use common::load;
+
+/*
+Every frame in tests/corpus must decode and re-encode to the exact same bytes.
+*/
+
+fn round_trip(name: &str) -> Packet {
+    let data = load(name);
+    let packet = Packet::new(Interfaces::Ethernet, 0, &data).unwrap();
+    assert_eq!(packet.to_bytes(), data, "{} didn't round trip", name);
+    assert_eq!(packet.len(), data.len(), "{} length mismatch", name);
+    packet
+}
+
+fn ethernet(packet: &Packet) -> &EthernetFrame {
+    packet.get_frame().as_any().downcast_ref::<EthernetFrame>().unwrap()
+}
+
+fn child<T: Layer + 'static>(layer: Option<&impl AsRef<dyn Layer>>) -> &T {
+    layer.unwrap().as_ref().as_any().downcast_ref::<T>().unwrap()
+}
+
+#[test]
+fn corpus() {
+    let names = common::names();
+    assert!(!names.is_empty());
+
+    for name in names {
+        round_trip(&name);
+    }
+}
+
+#[test]
+fn arp() {
+    let packet = round_trip("arp_request");
+    let frame = ethernet(&packet);
+    assert_eq!(frame.get_trailer().len(), 18);
+    assert_eq!(child::<ArpExtension>(frame.get_data()).get_target_address().octets(), [192, 168, 0, 1]);
+
+    let packet = round_trip("arp_reply");
+    assert_eq!(ethernet(&packet).get_trailer().len(), 22);
+
+    //InARP REQUEST AND AN OPCODE NOBODY ASSIGNED STILL DECODE
+    for (code, opcode) in [(8, ArpOperations::InArp), (0x1234, ArpOperations::Unknown(0x1234))] {
+        let mut data = load("arp_request");
+        data[20..22].copy_from_slice(&(code as u16).to_be_bytes());
+        let packet = Packet::new(Interfaces::Ethernet, 0, &data).unwrap();
+        assert_eq!(child::<ArpExtension>(ethernet(&packet).get_data()).get_opcode(), opcode);
+        assert_eq!(packet.to_bytes(), data);
+    }
+
+    //ONLY MAC AND IPv4 SIZES ARE DECODED, ANYTHING ELSE IS KEPT RAW
+    let mut data = load("arp_request");
+    data[19] = 16;
+    let packet = Packet::new(Interfaces::Ethernet, 0, &data).unwrap();
+    assert_eq!(child::<RawLayer>(ethernet(&packet).get_data()).get_code(), 0x0806);
+    assert_eq!(packet.to_bytes(), data);
+}
+
+#[test]
+fn ipv4_tcp() {
+    let packet = round_trip("ipv4_tcp_syn_options");
+    let ipv4 = child::<Ipv4Layer>(ethernet(&packet).get_data());
+    assert!(ipv4.validate_checksum());
+    let tcp = child::<TcpLayer>(ipv4.get_data());
+    assert_eq!(tcp.get_data_offset(), 40);
+    assert_eq!(tcp.get_options().len(), 20);
+    assert!(tcp.get_payload().is_none());
+
+    let packet = round_trip("ipv4_options_tcp_payload");
+    let ipv4 = child::<Ipv4Layer>(ethernet(&packet).get_data());
+    assert_eq!(ipv4.get_ihl(), 6);
+    assert_eq!(ipv4.get_options(), &vec![0x94, 0x04, 0x00, 0x00]);
+    assert!(ipv4.validate_checksum());
+    let tcp = child::<TcpLayer>(ipv4.get_data());
+    assert_eq!(tcp.get_payload().as_deref(), Some(&b"GET / HTTP/1.1\r\n\r\n"[..]));
+
+    let packet = round_trip("ipv4_tcp_ack_padded");
+    assert_eq!(ethernet(&packet).get_trailer().len(), 6);
+}
+
+#[test]
+fn ipv4_udp() {
+    let packet = round_trip("ipv4_udp_dns");
+    let udp = child::<UdpLayer>(child::<Ipv4Layer>(ethernet(&packet).get_data()).get_data());
+    assert_eq!(udp.get_destination_port(), 53);
+    assert_eq!(udp.get_type(), UdpTypes::Unknown);
+
+    let packet = round_trip("ipv4_udp_dhcp_discover");
+    let udp = child::<UdpLayer>(child::<Ipv4Layer>(ethernet(&packet).get_data()).get_data());
+    assert_eq!(udp.get_type(), UdpTypes::Dhcp);
+}
+
+#[test]
+fn ipv4_icmp() {
+    let packet = round_trip("ipv4_icmp_echo");
+    let icmp = child::<IcmpLayer>(child::<Ipv4Layer>(ethernet(&packet).get_data()).get_data());
+    assert_eq!(icmp.get_type(), 8);
+
+    let packet = round_trip("ipv4_icmp_short_padded");
+    assert_eq!(ethernet(&packet).get_trailer().len(), 18);
+}
+
+#[test]
+fn ipv4_raw() {
+    let packet = round_trip("ipv4_gre");
+    assert_eq!(child::<RawLayer>(child::<Ipv4Layer>(ethernet(&packet).get_data()).get_data()).get_code(), 47);
+
+    let packet = round_trip("ipv4_udp_fragment");
+    let ipv4 = child::<Ipv4Layer>(ethernet(&packet).get_data());
+    assert_eq!(ipv4.get_fragment_offset(), 185);
+    assert_eq!(child::<RawLayer>(ipv4.get_data()).get_code(), 17);
+}
+
+#[test]
+fn ipv6() {
+    let packet = round_trip("ipv6_tcp");
+    let tcp = child::<TcpLayer>(child::<Ipv6Layer>(ethernet(&packet).get_data()).get_data());
+    assert_eq!(tcp.get_options().len(), 4);
+
+    let packet = round_trip("ipv6_udp");
+    assert_eq!(child::<UdpLayer>(child::<Ipv6Layer>(ethernet(&packet).get_data()).get_data()).get_source_port(), 5353);
+
+    let packet = round_trip("ipv6_icmpv6_echo");
+    assert_eq!(child::<Icmpv6Layer>(child::<Ipv6Layer>(ethernet(&packet).get_data()).get_data()).get_type(), 128);
+
+    let packet = round_trip("ipv6_hop_by_hop");
+    assert_eq!(child::<RawLayer>(child::<Ipv6Layer>(ethernet(&packet).get_data()).get_data()).get_code(), 0);
+}
+
+#[test]
+fn vlan() {
+    let packet = round_trip("vlan_ipv4_udp_dns");
+    assert_eq!(ethernet(&packet).get_type(), Types::Vlan);
+    let vlan = child::<VlanLayer>(ethernet(&packet).get_data());
+    assert_eq!(vlan.get_vlan_id(), 100);
+    assert_eq!(vlan.get_priority(), 5);
+    assert_eq!(vlan.get_type(), Types::IPv4);
+    assert_eq!(packet.layer::<UdpLayer>().unwrap().get_destination_port(), 53);
+
+    let packet = round_trip("qinq_ipv4_icmp_padded");
+    assert_eq!(ethernet(&packet).get_type(), Types::QinQ);
+    assert_eq!(ethernet(&packet).get_trailer().len(), 18);
+    let vlans = packet.layers().filter_map(|l| l.as_any().downcast_ref::<VlanLayer>()).map(|v| v.get_vlan_id()).collect::<Vec<_>>();
+    assert_eq!(vlans, vec![10, 20]);
+    assert!(packet.layer::<IcmpLayer>().is_some());
+}
+
+#[test]
+fn unknown_ether_type() {
+    let packet = round_trip("lldp");
+    assert_eq!(child::<RawLayer>(ethernet(&packet).get_data()).get_code(), 0x88cc);
+}
+
+#[test]
+fn layer_lookup() {
+    for name in ["ipv4_udp_dns", "ipv6_udp"] {
+        let mut packet = round_trip(name);
+        assert!(packet.layer::<UdpLayer>().is_some());
+        assert!(packet.layer::<TcpLayer>().is_none());
+        assert_eq!(packet.layers().count(), 3);
+
+        packet.layer_mut::<UdpLayer>().unwrap().set_destination_port(5300);
+        assert_eq!(packet.layer::<UdpLayer>().unwrap().get_destination_port(), 5300);
+    }
+
+    let packet = round_trip("ipv4_udp_dhcp_discover");
+    assert!(packet.layers().last().unwrap().is::<DhcpLayer>());
+}
+
+#[test]
+fn views() {
+    let data = load("ipv4_options_tcp_payload");
+    let packet = round_trip("ipv4_options_tcp_payload");
+
+    let ethernet = EthernetView::new(&data).unwrap();
+    assert_eq!(ethernet.get_type(), Types::IPv4);
+
+    let ipv4 = Ipv4View::new(ethernet.get_payload()).unwrap();
+    assert!(ipv4.validate_checksum());
+    assert_eq!(ipv4.get_options(), packet.layer::<Ipv4Layer>().unwrap().get_options().as_slice());
+
+    let tcp = TcpView::new(ipv4.get_payload()).unwrap();
+    assert_eq!(tcp.get_payload(), b"GET / HTTP/1.1\r\n\r\n");
+    assert_eq!(tcp.to_layer().unwrap().to_bytes(), packet.layer::<TcpLayer>().unwrap().to_bytes());
+
+    let data = load("ipv6_udp");
+    let ipv6 = Ipv6View::new(EthernetView::new(&data).unwrap().get_payload()).unwrap();
+    assert_eq!(UdpView::new(ipv6.get_payload()).unwrap().get_source_port(), 5353);
+
+    assert!(Ipv4View::new(&data[14..30]).is_err());
+}
+
+#[test]
+fn finalize() {
+    for name in ["ipv4_options_tcp_payload", "ipv4_udp_dns", "ipv4_icmp_echo", "ipv6_tcp", "ipv6_udp", "ipv6_icmpv6_echo"] {
+        let data = load(name);
+        let mut packet = round_trip(name);
+        packet.finalize();
+        assert_eq!(packet.to_bytes(), data, "{} checksums changed", name);
+    }
+
+    let mut packet = round_trip("ipv4_options_tcp_payload");
+    packet.layer_mut::<TcpLayer>().unwrap().set_payload(b"GET /index.html HTTP/1.1\r\n\r\n".to_vec());
+    packet.finalize();
+
+    let packet = Packet::new(Interfaces::Ethernet, 0, &packet.to_bytes()).unwrap();
+    let ipv4 = packet.layer::<Ipv4Layer>().unwrap();
+    assert!(ipv4.validate_checksum());
+    assert_eq!(ipv4.get_total_length(), 24 + 20 + 28);
+    let source_address = IpAddr::V4(ipv4.get_source_address());
+    let destination_address = IpAddr::V4(ipv4.get_destination_address());
+    assert!(packet.layer::<TcpLayer>().unwrap().validate_checksum(source_address, destination_address));
+}
+
+#[test]
+fn truncated() {
+    let data = load("ipv4_tcp_syn_options");
+    assert!(Packet::new(Interfaces::Ethernet, 0, &data[..54]).is_err());
+
+    let packet = Packet::with_original_length(Interfaces::Ethernet, 0, &data[..54], data.len()).unwrap();
+    assert!(packet.is_truncated());
+    assert_eq!(packet.get_captured_length(), 54);
+    assert_eq!(packet.get_original_length(), data.len());
+    assert!(packet.layer::<Ipv4Layer>().unwrap().get_data().is_none());
+    assert!(packet.layer::<TcpLayer>().is_none());
+    assert_eq!(packet.get_undecoded(), &data[34..54]);
+    assert_eq!(packet.len(), 54);
+    assert_eq!(packet.to_bytes(), &data[..54]);
+
+    let mut writer = PcapWriter::new(Vec::new(), Interfaces::Ethernet, TimestampPrecisions::Micro).unwrap();
+    writer.write_packet(&packet).unwrap();
+    let buf = writer.into_inner();
+
+    let packet = PcapReader::new(buf.as_slice()).unwrap().next_packet().unwrap();
+    assert!(packet.is_truncated());
+    assert_eq!(packet.get_original_length(), data.len());
+    assert_eq!(packet.to_bytes(), &data[..54]);
+
+    let mut writer = PcapWriter::new(Vec::new(), Interfaces::Ethernet, TimestampPrecisions::Micro).unwrap();
+    writer.write_packet(&packet).unwrap();
+    assert_eq!(writer.into_inner(), buf);
+
+    let packet = round_trip("ipv4_tcp_syn_options");
+    assert!(!packet.is_truncated());
+}
+
+#[test]
+fn checksums() {
+    for name in ["ipv4_tcp_syn_options", "ipv4_udp_dns", "ipv4_icmp_echo", "ipv6_tcp", "ipv6_udp", "ipv6_icmpv6_echo", "qinq_ipv4_icmp_padded"] {
+        assert_eq!(round_trip(name).validate(), Ok(()), "{} failed validation", name);
+    }
+
+    //THE DHCP DISCOVER IN THE CORPUS WAS WRITTEN WITH A WRONG UDP CHECKSUM, IT STILL DECODES
+    let data = load("ipv4_udp_dhcp_discover");
+    assert_eq!(round_trip("ipv4_udp_dhcp_discover").validate(), Err(DecodeError::BadChecksum {
+        layer: "UDP",
+        offset: 34,
+        expected: 0xb423,
+        found: 0x32cf
+    }));
+    assert!(decode_packet_checked(Interfaces::Ethernet, &data).is_err());
+
+    let mut data = load("ipv4_tcp_syn_options");
+    data[24] ^= 0xFF;
+    let error = Packet::new(Interfaces::Ethernet, 0, &data).unwrap().validate().unwrap_err();
+    assert_eq!((error.get_layer(), error.get_offset()), ("IPv4", 14));
+
+    let mut data = load("ipv4_tcp_syn_options");
+    data[50] ^= 0xFF;
+    let error = decode_packet_checked(Interfaces::Ethernet, &data).unwrap_err();
+    assert_eq!((error.get_layer(), error.get_offset()), ("TCP", 34));
+
+    let mut data = load("ipv6_icmpv6_echo");
+    data[56] ^= 0xFF;
+    let error = decode_packet_checked(Interfaces::Ethernet, &data).unwrap_err();
+    assert_eq!((error.get_layer(), error.get_offset()), ("ICMPv6", 54));
+
+    //CUT CAPTURES ONLY HAVE THEIR IPv4 HEADER CHECKED
+    let data = load("ipv4_options_tcp_payload");
+    let packet = Packet::with_original_length(Interfaces::Ethernet, 0, &data[..70], data.len()).unwrap();
+    assert!(packet.layer::<TcpLayer>().is_some());
+    assert_eq!(packet.validate(), Ok(()));
+}